@@ -0,0 +1,377 @@
+//! ## Batch Sending
+//! This module contains the pieces shared by the chunked batch sends,
+//! [`ZeptoMailClient::send_batch_email_chunked`] and [`ZeptoMailClient::send_batch_template_email_chunked`].
+//!
+//! ZeptoMail rejects batch requests with more than [`MAX_BATCH_RECIPIENTS`] recipients. The chunked
+//! sends split the recipients into requests below that cap, copy every other field to each of them,
+//! and send them with bounded concurrency.
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::models::suppression::normalize_address;
+use crate::request::Endpoint;
+use crate::suppression::guard::SuppressionTarget;
+use crate::validation::Validate;
+use crate::{ApiErrorDetail, ApiResponse, EmailAddress, Recipient, ZeptoErrorCode, ZeptoMailClient, ZeptoMailError};
+
+/// The most recipients ZeptoMail accepts in a single batch request.
+pub const MAX_BATCH_RECIPIENTS: usize = 500;
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How a chunked batch send splits and sends its recipients.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::batch::ChunkOptions;
+///
+/// let options = ChunkOptions::new().chunk_size(200).concurrency(2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkOptions {
+    pub(crate) chunk_size: usize,
+    pub(crate) concurrency: usize,
+}
+
+impl ChunkOptions {
+    /// Chunks of [`MAX_BATCH_RECIPIENTS`] recipients, sent four at a time.
+    pub fn new() -> Self {
+        ChunkOptions {
+            chunk_size: MAX_BATCH_RECIPIENTS,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Sets the number of recipients per request, between 1 and [`MAX_BATCH_RECIPIENTS`].
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.clamp(1, MAX_BATCH_RECIPIENTS);
+        self
+    }
+
+    /// Sets how many requests are in flight at the same time, at least 1.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The outcome of one request of a chunked batch send.
+#[derive(Debug)]
+pub struct ChunkOutcome {
+    //The position of the chunk, counting from 0.
+    pub index: usize,
+    //The recipients the chunk was sent to, in the order of the original request.
+    pub recipients: Vec<Recipient>,
+    pub result: Result<ApiResponse, ZeptoMailError>,
+}
+
+impl ChunkOutcome {
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// The outcomes of all requests of a chunked batch send, in chunk order.
+#[derive(Debug)]
+pub struct ChunkedSendReport {
+    pub chunks: Vec<ChunkOutcome>,
+    //The recipients the suppression store dropped before the request was split.
+    pub suppressed: Vec<Recipient>,
+}
+
+impl ChunkedSendReport {
+    /// Whether every chunk was accepted.
+    pub fn is_success(&self) -> bool {
+        self.chunks.iter().all(ChunkOutcome::is_success)
+    }
+
+    /// Every recipient, in the order of the original request, with the outcome of its chunk.
+    pub fn recipients(&self) -> impl Iterator<Item = (&Recipient, &Result<ApiResponse, ZeptoMailError>)> {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| chunk.recipients.iter().map(move |recipient| (recipient, &chunk.result)))
+    }
+
+    /// The recipients whose chunk was accepted.
+    pub fn accepted_recipients(&self) -> impl Iterator<Item = &Recipient> {
+        self.recipients().filter(|(_, result)| result.is_ok()).map(|(recipient, _)| recipient)
+    }
+
+    /// The recipients whose chunk failed, e.g. to retry them later.
+    pub fn failed_recipients(&self) -> impl Iterator<Item = &Recipient> {
+        self.recipients().filter(|(_, result)| result.is_err()).map(|(recipient, _)| recipient)
+    }
+
+    /// A serializable report with the outcome for every recipient, followed by the suppressed recipients.
+    pub fn report(&self) -> BatchSendReport {
+        let suppressed = self.suppressed.iter().map(|recipient| RecipientOutcome {
+            recipient: recipient.clone(),
+            status: RecipientStatus::Suppressed,
+            request_id: None,
+            chunk_index: None,
+            error: None,
+        });
+
+        BatchSendReport {
+            recipients: self
+                .chunks
+                .iter()
+                .flat_map(|chunk| outcomes(chunk.index, &chunk.recipients, &chunk.result))
+                .chain(suppressed)
+                .collect(),
+        }
+    }
+}
+
+/// Whether ZeptoMail accepted the email for a recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecipientStatus {
+    Accepted,
+    Rejected,
+    //Not sent to because the address is in the client's suppression store.
+    Suppressed,
+}
+
+/// Why the email for a recipient was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipientError {
+    //The ZeptoMail error code, if the API returned one. The code of a detail about this recipient
+    //wins over the general code of the error.
+    pub code: Option<ZeptoErrorCode>,
+    pub message: String,
+    //The field the error refers to, e.g. `recipients[2].email_address.address`.
+    pub target: Option<String>,
+    pub http_status: Option<u16>,
+}
+
+/// The outcome of a batch send for one recipient.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecipientOutcome {
+    pub recipient: Recipient,
+    pub status: RecipientStatus,
+    //The ID ZeptoMail assigned to the request the recipient was part of.
+    pub request_id: Option<String>,
+    //The chunk the recipient was sent in, 0 unless the batch was sent in chunks. `None` for
+    //suppressed recipients, who are not sent in any chunk.
+    pub chunk_index: Option<usize>,
+    pub error: Option<RecipientError>,
+}
+
+/// The outcome of a batch send for every recipient, in the order of the request.
+///
+/// The report can be stored as JSON, e.g. in an audit table.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{BatchEmailRequest, ZeptoMailError, ApiError};
+/// use zeptomail_rs::batch::{BatchSendReport, RecipientStatus};
+///
+/// let batch_email_request = BatchEmailRequest::builder()
+///     .sender("sender@example.com")
+///     .to("recipient1@example.com")
+///     .to("recipient2@example.com")
+///     .subject("Batch Email Test")
+///     .text("Batch Email Content")
+///     .build()
+///     .unwrap();
+///
+/// let api_error: ApiError = serde_json::from_str(r#"{
+///     "code": "TM_3301",
+///     "message": "Invalid data",
+///     "details": [{ "code": "SM_111", "message": "Invalid recipient", "target": "recipients[1].email_address.address" }],
+///     "request_id": "2d6f.1e0b"
+/// }"#).unwrap();
+/// let result = Err(ZeptoMailError::ApiError(api_error));
+///
+/// let report = BatchSendReport::from_result(&batch_email_request.recipients, &result);
+///
+/// assert_eq!(report.recipients[1].status, RecipientStatus::Rejected);
+/// assert_eq!(report.recipients[1].error.as_ref().unwrap().message, "Invalid recipient");
+/// assert_eq!(report.recipients[0].error.as_ref().unwrap().message, "Invalid data");
+/// println!("{}", serde_json::to_string(&report).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BatchSendReport {
+    pub recipients: Vec<RecipientOutcome>,
+}
+
+impl BatchSendReport {
+    /// Correlates `recipients` with the result of the request they were sent in.
+    ///
+    /// ZeptoMail accepts or rejects a batch request as a whole, so every recipient shares the
+    /// outcome. When a rejection has a detail about a specific recipient, that recipient's error
+    /// is the detail.
+    pub fn from_result(
+        recipients: &[Recipient],
+        result: &Result<ApiResponse, ZeptoMailError>
+    ) -> Self {
+        BatchSendReport {
+            recipients: outcomes(0, recipients, result).collect(),
+        }
+    }
+
+    /// Whether the email was accepted for every recipient that was not suppressed.
+    pub fn is_success(&self) -> bool {
+        self.recipients.iter().all(|outcome| outcome.status != RecipientStatus::Rejected)
+    }
+
+    pub fn accepted(&self) -> impl Iterator<Item = &RecipientOutcome> {
+        self.recipients.iter().filter(|outcome| outcome.status == RecipientStatus::Accepted)
+    }
+
+    pub fn rejected(&self) -> impl Iterator<Item = &RecipientOutcome> {
+        self.recipients.iter().filter(|outcome| outcome.status == RecipientStatus::Rejected)
+    }
+
+    pub fn suppressed(&self) -> impl Iterator<Item = &RecipientOutcome> {
+        self.recipients.iter().filter(|outcome| outcome.status == RecipientStatus::Suppressed)
+    }
+}
+
+impl From<&ChunkedSendReport> for BatchSendReport {
+    fn from(report: &ChunkedSendReport) -> Self {
+        report.report()
+    }
+}
+
+fn outcomes<'a>(
+    chunk_index: usize,
+    recipients: &'a [Recipient],
+    result: &'a Result<ApiResponse, ZeptoMailError>
+) -> impl Iterator<Item = RecipientOutcome> + 'a {
+    recipients.iter().enumerate().map(move |(index, recipient)| match result {
+        _ if is_suppressed(result, recipient) => RecipientOutcome {
+            recipient: recipient.clone(),
+            status: RecipientStatus::Suppressed,
+            request_id: result.as_ref().ok().map(|response| response.request_id.clone()),
+            chunk_index: None,
+            error: None,
+        },
+        Ok(response) => RecipientOutcome {
+            recipient: recipient.clone(),
+            status: RecipientStatus::Accepted,
+            request_id: Some(response.request_id.clone()),
+            chunk_index: Some(chunk_index),
+            error: None,
+        },
+        Err(error) => RecipientOutcome {
+            recipient: recipient.clone(),
+            status: RecipientStatus::Rejected,
+            request_id: error.request_id().map(str::to_string),
+            chunk_index: Some(chunk_index),
+            error: Some(recipient_error(error, index, recipient)),
+        },
+    })
+}
+
+//Whether the suppression store dropped the recipient from the request, or the send was rejected
+//because of it.
+fn is_suppressed(result: &Result<ApiResponse, ZeptoMailError>, recipient: &Recipient) -> bool {
+    let suppressed: &[EmailAddress] = match result {
+        Ok(response) => &response.suppressed,
+        Err(ZeptoMailError::Suppressed { recipients }) => recipients,
+        Err(_) => return false,
+    };
+    let recipient: String = normalize_address(&recipient.email_address.address);
+    suppressed.iter().any(|address| normalize_address(&address.address) == recipient)
+}
+
+fn recipient_error(error: &ZeptoMailError, index: usize, recipient: &Recipient) -> RecipientError {
+    let ZeptoMailError::ApiError(api_error) = error.last_error() else {
+        return RecipientError {
+            code: None,
+            message: error.to_string(),
+            target: None,
+            http_status: error.status().map(|status| status.as_u16()),
+        };
+    };
+
+    let detail: Option<&ApiErrorDetail> = api_error
+        .details
+        .iter()
+        .flatten()
+        .find(|detail| detail.target.as_deref().is_some_and(|target| is_about(target, index, recipient)));
+
+    match detail {
+        Some(detail) => RecipientError {
+            code: Some(detail.code.clone()),
+            message: detail.message.clone(),
+            target: detail.target.clone(),
+            http_status: api_error.http_status,
+        },
+        None => RecipientError {
+            code: Some(api_error.code.clone()),
+            message: api_error.message.clone(),
+            target: None,
+            http_status: api_error.http_status,
+        },
+    }
+}
+
+//Whether an error target, e.g. `recipients[2].email_address.address` or the address itself, refers
+//to the recipient at `index`.
+fn is_about(target: &str, index: usize, recipient: &Recipient) -> bool {
+    target.starts_with(&format!("recipients[{}]", index))
+        || target.eq_ignore_ascii_case(&recipient.email_address.address)
+}
+
+/// A batch request whose recipients can be split over several requests.
+pub(crate) trait BatchRequest: Endpoint<Response = ApiResponse> + Validate + SuppressionTarget + Sized {
+    fn recipients(&self) -> &[Recipient];
+
+    /// A copy of the request with only `recipients`. `carbon_copy` and `blind_carbon_copy` are kept
+    /// only if `with_copies` is set.
+    fn with_recipients(&self, recipients: Vec<Recipient>, with_copies: bool) -> Self;
+}
+
+impl ZeptoMailClient {
+    /// Splits `request` into chunks and sends them. Fails only if `request` as a whole is rejected by
+    /// the suppression policy, or does not pass validation once suppressed addresses are dropped; the
+    /// outcome of each chunk is in the report.
+    pub(crate) async fn send_chunked<R: BatchRequest>(
+        &self,
+        mut request: R,
+        options: ChunkOptions
+    ) -> Result<ChunkedSendReport, ZeptoMailError> {
+        //Validated after filtering, as unchunked sends are, so that what is validated is what is sent.
+        let suppressed: Vec<Recipient> = self.apply_suppressions(&mut request).await?;
+        if self.validate_requests {
+            request
+                .validate_with(&self.validation_rules)
+                .map_err(ZeptoMailError::Validation)?;
+        }
+
+        let chunks: Vec<(usize, R)> = request
+            .recipients()
+            .chunks(options.chunk_size)
+            .enumerate()
+            .map(|(index, recipients)| (index, request.with_recipients(recipients.to_vec(), index == 0)))
+            .collect();
+
+        let mut outcomes: Vec<ChunkOutcome> = futures_util::stream::iter(chunks)
+            .map(|(index, chunk)| async move {
+                let result: Result<ApiResponse, ZeptoMailError> = self.execute(&chunk).await;
+                ChunkOutcome {
+                    index,
+                    recipients: chunk.recipients().to_vec(),
+                    result,
+                }
+            })
+            .buffer_unordered(options.concurrency)
+            .collect()
+            .await;
+        outcomes.sort_by_key(|outcome| outcome.index);
+
+        Ok(ChunkedSendReport { chunks: outcomes, suppressed })
+    }
+}
@@ -1,24 +1,357 @@
+use std::sync::Arc;
 use std::time::Duration;
-use reqwest::Client;
-use crate::ZeptoMailError;
+use reqwest::{Certificate, Client, Proxy};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use crate::suppression::guard::{SuppressionPolicy, SuppressionStore};
+use crate::transport::{ReqwestTransport, Transport};
+use crate::validation::ValidationRules;
+use crate::{RateLimiter, Region, RetryPolicy, ZeptoMailError};
 
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+const DEFAULT_USER_AGENT: &str = concat!("zeptomail_rs/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone)]
 pub struct ZeptoMailClient {
-    pub client: Client,
-    pub api_key: String,
-    pub base_url: String,
+    pub(crate) transport: Arc<dyn Transport>,
+    //The `Zoho-enczapikey` authorization header, marked as sensitive.
+    pub(crate) auth_header: HeaderValue,
+    //Base URL of the sending and template APIs.
+    pub(crate) base_url: String,
+    //Base URL of the file cache upload API.
+    pub(crate) upload_url: String,
+    //Responses with a larger body are rejected with `ZeptoMailError::ResponseTooLarge`.
+    pub(crate) max_response_bytes: usize,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) validate_requests: bool,
+    pub(crate) validation_rules: ValidationRules,
+    pub(crate) suppression_store: Option<Arc<dyn SuppressionStore>>,
+    pub(crate) suppression_policy: SuppressionPolicy,
 }
 
 impl ZeptoMailClient {
+    /// Creates a client for an account hosted in the given `region`, using the default settings.
+    ///
+    /// Use [`ZeptoMailClient::builder`] to customise timeouts, proxies and other HTTP settings.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region};
+    ///
+    /// let client = ZeptoMailClient::new("your_api_key", Region::Us).unwrap();
+    /// assert_eq!(client.base_url(), "https://api.zeptomail.com/v1.1");
+    /// ```
     pub fn new(
-        api_key: &str
+        api_key: &str,
+        region: Region
     ) -> Result<Self, ZeptoMailError> {
-        Ok(ZeptoMailClient {
-            client: Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .map_err(ZeptoMailError::NetworkError)?,
+        Self::builder(api_key, region).build()
+    }
+
+    /// Creates a client that sends every request, including file uploads, to `base_url`.
+    ///
+    /// This is meant for proxies and test servers. `base_url` should include the API version,
+    /// e.g. `http://localhost:8080/v1.1`.
+    pub fn with_base_url(
+        api_key: &str,
+        base_url: &str
+    ) -> Result<Self, ZeptoMailError> {
+        Self::builder(api_key, Region::Eu)
+            .base_url(base_url)
+            .upload_url(base_url)
+            .build()
+    }
+
+    /// Returns a [`ZeptoMailClientBuilder`] for an account hosted in the given `region`.
+    pub fn builder(
+        api_key: &str,
+        region: Region
+    ) -> ZeptoMailClientBuilder {
+        ZeptoMailClientBuilder::new(api_key, region)
+    }
+
+    /// The base URL of the sending and template APIs.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The base URL of the file cache upload API.
+    pub fn upload_url(&self) -> &str {
+        &self.upload_url
+    }
+}
+
+/// Builder for a [`ZeptoMailClient`] with custom HTTP settings.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use zeptomail_rs::{ZeptoMailClient, Region};
+///
+/// let client = ZeptoMailClient::builder("your_api_key", Region::In)
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(60))
+///     .proxy(reqwest::Proxy::all("socks5://127.0.0.1:1080").unwrap())
+///     .user_agent("my-service/1.0")
+///     .build()
+///     .unwrap();
+/// ```
+pub struct ZeptoMailClientBuilder {
+    api_key: String,
+    region: Region,
+    base_url: Option<String>,
+    upload_url: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    root_certificates: Vec<Certificate>,
+    user_agent: String,
+    default_headers: HeaderMap,
+    http_client: Option<Client>,
+    transport: Option<Arc<dyn Transport>>,
+    max_response_bytes: usize,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    validate_requests: bool,
+    validation_rules: ValidationRules,
+    suppression_store: Option<Arc<dyn SuppressionStore>>,
+    suppression_policy: SuppressionPolicy,
+}
+
+impl ZeptoMailClientBuilder {
+    pub fn new(
+        api_key: &str,
+        region: Region
+    ) -> Self {
+        ZeptoMailClientBuilder {
             api_key: api_key.to_string(),
-            base_url: "https://api.zeptomail.eu/v1.1".to_string(),
+            region,
+            base_url: None,
+            upload_url: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            proxies: Vec::new(),
+            root_certificates: Vec::new(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            default_headers: HeaderMap::new(),
+            http_client: None,
+            transport: None,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            validate_requests: true,
+            validation_rules: ValidationRules::default(),
+            suppression_store: None,
+            suppression_policy: SuppressionPolicy::default(),
+        }
+    }
+
+    /// Overrides the base URL of the sending and template APIs picked from the region.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Overrides the base URL of the file cache upload API picked from the region.
+    pub fn upload_url(mut self, upload_url: &str) -> Self {
+        self.upload_url = Some(upload_url.to_string());
+        self
+    }
+
+    /// Sets the total timeout of a request, from connecting until the response body is read.
+    /// Defaults to 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Disables the request timeout.
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Adds an HTTP, HTTPS or SOCKS proxy.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trusts an additional root certificate, e.g. the one of an intercepting corporate proxy.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Sets the `User-Agent` header. Defaults to `zeptomail_rs/<version>`.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Adds a header that is sent with every request.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Adds all `headers` to the headers sent with every request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Uses a pre-built `reqwest::Client`, e.g. to share one connection pool across services.
+    ///
+    /// The client is used as-is: timeouts, proxies, certificates, the user agent and default
+    /// headers set on this builder are ignored.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the largest response body the client accepts, in bytes. Defaults to 4 MiB.
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = bytes;
+        self
+    }
+
+    /// Retries transient failures according to `retry_policy`. By default, requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limits the rate of requests and recipients with `rate_limiter`, which is shared by all clones
+    /// of the client. Every attempt, including retries, takes capacity.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Whether requests are checked with [`Validate`](crate::validation::Validate) before they are
+    /// sent. Enabled by default.
+    ///
+    /// Request builders only check that the sender and recipients are set, so with validation
+    /// turned off every other check is left to ZeptoMail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zeptomail_rs::{EmailRequest, Region, ZeptoMailClient};
+    /// use zeptomail_rs::transport::MemoryTransport;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let transport = MemoryTransport::new();
+    ///     transport.push_json(200, serde_json::json!({ "data": [], "message": "OK", "object": "email", "request_id": "2d6f.1e0b" }));
+    ///
+    ///     let client = ZeptoMailClient::builder("your_api_key", Region::Eu)
+    ///         .transport(transport)
+    ///         .validate_requests(false)
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     //No subject or body, which the client would otherwise reject.
+    ///     let email_request = EmailRequest::builder()
+    ///         .sender("sender@example.com")
+    ///         .to("recipient@example.com")
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     assert!(client.send_email(email_request).await.is_ok());
+    /// }
+    /// ```
+    pub fn validate_requests(mut self, validate_requests: bool) -> Self {
+        self.validate_requests = validate_requests;
+        self
+    }
+
+    /// Sets the rules requests are validated against, e.g. a custom list of blocked attachment
+    /// extensions. Defaults to [`ValidationRules::default`].
+    pub fn validation_rules(mut self, validation_rules: ValidationRules) -> Self {
+        self.validation_rules = validation_rules;
+        self
+    }
+
+    /// Checks every recipient, CC and BCC against `suppression_store` before an email is sent.
+    pub fn suppression_store(mut self, suppression_store: impl SuppressionStore + 'static) -> Self {
+        self.suppression_store = Some(Arc::new(suppression_store));
+        self
+    }
+
+    /// Sets whether suppressed addresses are dropped from a send or reject it. Defaults to
+    /// [`SuppressionPolicy::Filter`].
+    pub fn suppression_policy(mut self, suppression_policy: SuppressionPolicy) -> Self {
+        self.suppression_policy = suppression_policy;
+        self
+    }
+
+    /// Sends requests through a custom [`Transport`] instead of `reqwest`, e.g. a
+    /// [`MemoryTransport`](crate::transport::MemoryTransport) in tests.
+    ///
+    /// All HTTP settings on this builder, including [`http_client`](Self::http_client), are ignored.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn build(self) -> Result<ZeptoMailClient, ZeptoMailError> {
+        let transport: Arc<dyn Transport> = match (self.transport, self.http_client) {
+            (Some(transport), _) => transport,
+            (None, Some(client)) => Arc::new(ReqwestTransport::new(client)),
+            (None, None) => {
+                let mut builder = Client::builder()
+                    .user_agent(self.user_agent)
+                    .default_headers(self.default_headers);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+
+                Arc::new(ReqwestTransport::new(builder.build().map_err(ZeptoMailError::NetworkError)?))
+            }
+        };
+
+        let mut auth_header: HeaderValue = HeaderValue::from_str(&format!("Zoho-enczapikey {}", self.api_key))
+            .map_err(|_| ZeptoMailError::InvalidConfiguration(
+                "The API key contains characters that are not allowed in an HTTP header".to_string()
+            ))?;
+        auth_header.set_sensitive(true);
+
+        let base_url: String = self.base_url.unwrap_or_else(|| self.region.api_url());
+        let upload_url: String = self.upload_url.unwrap_or_else(|| self.region.upload_url());
+
+        Ok(ZeptoMailClient {
+            transport,
+            auth_header,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            upload_url: upload_url.trim_end_matches('/').to_string(),
+            max_response_bytes: self.max_response_bytes,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            validate_requests: self.validate_requests,
+            validation_rules: self.validation_rules,
+            suppression_store: self.suppression_store,
+            suppression_policy: self.suppression_policy,
         })
     }
 }
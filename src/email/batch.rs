@@ -1,14 +1,17 @@
 //! ## Send Batch Email
 //! This module contains the implementation for sending batch emails using the ZeptoMail API.
 
-use reqwest::{Response, StatusCode};
+use reqwest::Method;
 
+use crate::batch::{BatchRequest, BatchSendReport, ChunkOptions, ChunkedSendReport};
+use crate::request::{Endpoint, Payload};
+use crate::validation::{Validate, ValidationErrors, ValidationRules};
 use crate::{
     ZeptoMailClient,
     BatchEmailRequest,
     ApiResponse,
+    Recipient,
     ZeptoMailError,
-    ApiError,
 };
 
 impl ZeptoMailClient {
@@ -32,58 +35,34 @@ impl ZeptoMailClient {
     /// # Errors
     ///
     /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the request fails validation before it is sent.
+    /// * `ZeptoMailError::Suppressed` - If the suppression store rejects the send, see [`SuppressionPolicy`](crate::suppression::guard::SuppressionPolicy).
     /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
     /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
-    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing or deserializing the request/response.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use zeptomail_rs::{ZeptoMailClient,BatchEmailRequest, EmailAddress, Recipient};
-    /// use std::collections::HashMap;
+    /// use zeptomail_rs::{ZeptoMailClient, Region, BatchEmailRequest, EmailAddress, Recipient};
     /// use std::error::Error;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let client = ZeptoMailClient::new("your_api_key")?;
-    ///
-    ///     let sender = EmailAddress {
-    ///         address: "sender@example.com".to_string(),
-    ///         name: Some("Sender Name".to_string()),
-    ///     };
-    ///
-    ///     let recipients = vec![
-    ///         Recipient {
-    ///             email_address: EmailAddress {
-    ///                 address: "recipient1@example.com".to_string(),
-    ///                 name: Some("Recipient One".to_string()),
-    ///             },
-    ///             merge_info: None,
-    ///         },
-    ///         Recipient {
-    ///             email_address: EmailAddress {
-    ///                 address: "recipient2@example.com".to_string(),
-    ///                 name: Some("Recipient Two".to_string()),
-    ///             },
-    ///             merge_info: None,
-    ///         },
-    ///     ];
-    ///
-    ///     let batch_email_request = BatchEmailRequest {
-    ///         sender,
-    ///         recipients,
-    ///         subject: "Batch Email Test".to_string(),
-    ///         htmlbody: Some("<div>Batch Email Content</div>".to_string()),
-    ///         textbody: Some("Batch Email Content".to_string()),
-    ///         carbon_copy: None,
-    ///         blind_carbon_copy: None,
-    ///         track_clicks: Some(true),
-    ///         track_opens: Some(true),
-    ///         client_reference: None,
-    ///         mime_headers: None,
-    ///         attachments: None,
-    ///         inline_images: None,
-    ///     };
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let batch_email_request = BatchEmailRequest::builder()
+    ///         .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+    ///         .to(Recipient::new("recipient1@example.com").with_name("Recipient One"))
+    ///         .to(Recipient::new("recipient2@example.com").with_name("Recipient Two"))
+    ///         .subject("Batch Email Test")
+    ///         .html("<div>Batch Email Content</div>")
+    ///         .text("Batch Email Content")
+    ///         .track_clicks(true)
+    ///         .track_opens(true)
+    ///         .build()?;
     ///
     ///     match client.send_batch_email(batch_email_request).await {
     ///         Ok(response) => println!("Email sent successfully: {:?}", response),
@@ -97,28 +76,120 @@ impl ZeptoMailClient {
         &self,
         batch_email_request: BatchEmailRequest
     ) -> Result<ApiResponse, ZeptoMailError> {
-        let endpoint: &str = "email/batch";
-        let url: String = format!("{}/{}", self.base_url, endpoint);
+        self.send_unsuppressed(batch_email_request).await
+    }
 
-        let response: Response = self.client
-            .post(&url)
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Zoho-enczapikey {}", self.api_key))
-            .json(&batch_email_request)
-            .send()
-            .await?;
+    /// Sends a batch email like [`send_batch_email`](Self::send_batch_email), and reports the outcome for every recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_email_request` - A `BatchEmailRequest` struct containing the details of the email.
+    ///
+    /// # Returns
+    ///
+    /// A `BatchSendReport` that marks every recipient as accepted or rejected, with the request ID and
+    /// the error, if any. Failures, including validation errors, are part of the report.
+    pub async fn send_batch_email_with_report(
+        &self,
+        batch_email_request: BatchEmailRequest
+    ) -> BatchSendReport {
+        let recipients: Vec<Recipient> = batch_email_request.recipients.clone();
+        let result: Result<ApiResponse, ZeptoMailError> = self.send_batch_email(batch_email_request).await;
+        BatchSendReport::from_result(&recipients, &result)
+    }
 
-        let status: StatusCode = response.status();
+    /// Sends a batch email in chunks of at most [`MAX_BATCH_RECIPIENTS`](crate::batch::MAX_BATCH_RECIPIENTS) recipients.
+    ///
+    /// The recipients are split into chunks of `options.chunk_size`. Every chunk is sent as its own
+    /// batch request with the same sender, content and settings, at most `options.concurrency`
+    /// at a time. `carbon_copy` and `blind_carbon_copy` are only sent with the first chunk, so they
+    /// receive a single copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_email_request` - A `BatchEmailRequest` with any number of recipients.
+    /// * `options` - The chunk size and concurrency.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(ChunkedSendReport)` - The outcome of every chunk, including failed ones.
+    /// * `Err(ZeptoMailError)` - If the request fails validation or is rejected by the suppression policy, in which case nothing is sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, BatchEmailRequest, Recipient};
+    /// use zeptomail_rs::batch::ChunkOptions;
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let mut builder = BatchEmailRequest::builder()
+    ///         .sender("newsletter@example.com")
+    ///         .subject("Our monthly newsletter")
+    ///         .html("<div>Hello {{name}}</div>");
+    ///     for index in 0..1_200 {
+    ///         let recipient = Recipient::new(format!("subscriber{}@example.com", index))
+    ///             .with_merge_field("name", format!("Subscriber {}", index));
+    ///         builder = builder.to(recipient);
+    ///     }
+    ///
+    ///     let report = client.send_batch_email_chunked(builder.build()?, ChunkOptions::new()).await?;
+    ///     for recipient in report.failed_recipients() {
+    ///         eprintln!("Not sent to {}", recipient.email_address.address);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn send_batch_email_chunked(
+        &self,
+        batch_email_request: BatchEmailRequest,
+        options: ChunkOptions
+    ) -> Result<ChunkedSendReport, ZeptoMailError> {
+        self.send_chunked(batch_email_request, options).await
+    }
+}
+
+impl Endpoint for BatchEmailRequest {
+    type Response = ApiResponse;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["email", "batch"]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(self)
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        self.validate_with(rules)
+    }
+
+    fn recipient_count(&self) -> u32 {
+        let copies: usize = self.carbon_copy.as_ref().map_or(0, Vec::len)
+            + self.blind_carbon_copy.as_ref().map_or(0, Vec::len);
+        (self.recipients.len() + copies) as u32
+    }
+}
+
+impl BatchRequest for BatchEmailRequest {
+    fn recipients(&self) -> &[Recipient] {
+        &self.recipients
+    }
 
-        if status.is_success() {
-            let response_text: String = response.text().await?;
-            let success_response: ApiResponse = serde_json::from_str(&response_text)?;
-            Ok(success_response)
-        } else {
-            let response_text: String = response.text().await?;
-            let api_error: ApiError = serde_json::from_str(&response_text)?;
-            Err(ZeptoMailError::ApiError(api_error))
+    fn with_recipients(&self, recipients: Vec<Recipient>, with_copies: bool) -> Self {
+        let copies = |copy: &Option<Vec<Recipient>>| copy.clone().filter(|_| with_copies);
+        BatchEmailRequest {
+            recipients,
+            carbon_copy: copies(&self.carbon_copy),
+            blind_carbon_copy: copies(&self.blind_carbon_copy),
+            ..self.clone()
         }
     }
 }
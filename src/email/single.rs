@@ -1,14 +1,15 @@
 //! ## Send Email
 //! This module contains the implementation for sending an email using the ZeptoMail API.
 
-use reqwest::{Response, StatusCode};
+use reqwest::Method;
 
+use crate::request::{Endpoint, Payload};
+use crate::validation::{Validate, ValidationErrors, ValidationRules};
 use crate::{
     ZeptoMailClient,
     EmailRequest,
     ApiResponse,
     ZeptoMailError,
-    ApiError,
 };
 
 impl ZeptoMailClient {
@@ -31,50 +32,33 @@ impl ZeptoMailClient {
     /// # Errors
     ///
     /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the request fails validation before it is sent.
+    /// * `ZeptoMailError::Suppressed` - If the suppression store rejects the send, see [`SuppressionPolicy`](crate::suppression::guard::SuppressionPolicy).
     /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
     /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
-    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing or deserializing the request/response.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use zeptomail_rs::{ZeptoMailClient, EmailRequest, EmailAddress, Recipient};
+    /// use zeptomail_rs::{ZeptoMailClient, Region, EmailRequest, EmailAddress};
     /// use std::error::Error;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let client = ZeptoMailClient::new("your_api_key")?;
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
     ///
-    ///     let sender = EmailAddress {
-    ///         address: "sender@example.com".to_string(),
-    ///         name: Some("Sender Name".to_string()),
-    ///     };
-    ///
-    ///     let recipient = Recipient {
-    ///         email_address: EmailAddress {
-    ///             address: "recipient@example.com".to_string(),
-    ///             name: Some("Recipient Name".to_string()),
-    ///         },
-    ///         merge_info: None,
-    ///     };
-    ///
-    ///     let email_request = EmailRequest {
-    ///         bounce_address: None,
-    ///         sender,
-    ///         recipients: vec![recipient],
-    ///         reply_to: None,
-    ///         subject: "Test Email".to_string(),
-    ///         htmlbody: Some("<div>Email Content</div>".to_string()),
-    ///         textbody: Some("Email Content".to_string()),
-    ///         carbon_copy: None,
-    ///         blind_carbon_copy: None,
-    ///         track_clicks: Some(true),
-    ///         track_opens: Some(true),
-    ///         client_reference: None,
-    ///         mime_headers: None,
-    ///         attachments: None,
-    ///         inline_images: None,
-    ///     };
+    ///     let email_request = EmailRequest::builder()
+    ///         .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+    ///         .to(EmailAddress::new("recipient@example.com").with_name("Recipient Name"))
+    ///         .subject("Test Email")
+    ///         .html("<div>Email Content</div>")
+    ///         .text("Email Content")
+    ///         .track_clicks(true)
+    ///         .track_opens(true)
+    ///         .build()?;
     ///
     ///     match client.send_email(email_request).await {
     ///         Ok(response) => println!("Email sent successfully: {:?}", response),
@@ -88,28 +72,30 @@ impl ZeptoMailClient {
         &self,
         email_request: EmailRequest
     ) -> Result<ApiResponse, ZeptoMailError> {
-        let endpoint: &str = "email";
-        let url: String = format!("{}/{}", self.base_url, endpoint);
+        self.send_unsuppressed(email_request).await
+    }
+}
 
-        let response: Response = self.client
-            .post(&url)
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Zoho-enczapikey {}", self.api_key))
-            .json(&email_request)
-            .send()
-            .await?;
+impl Endpoint for EmailRequest {
+    type Response = ApiResponse;
 
-        let status: StatusCode = response.status();
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["email"]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(self)
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        self.validate_with(rules)
+    }
 
-        if status.is_success() {
-            let response_text: String = response.text().await?;
-            let success_response: ApiResponse = serde_json::from_str(&response_text)?;
-            Ok(success_response)
-        } else {
-            let response_text: String = response.text().await?;
-            let api_error: ApiError = serde_json::from_str(&response_text)?;
-            Err(ZeptoMailError::ApiError(api_error))
-        }
+    fn recipient_count(&self) -> u32 {
+        let copies: usize = self.carbon_copy.as_ref().map_or(0, Vec::len)
+            + self.blind_carbon_copy.as_ref().map_or(0, Vec::len);
+        (self.recipients.len() + copies) as u32
     }
 }
\ No newline at end of file
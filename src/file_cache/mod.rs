@@ -1 +1,2 @@
-pub mod upload;
\ No newline at end of file
+pub mod registry;
+pub mod upload;
@@ -0,0 +1,386 @@
+//! ## File Cache Registry
+//! This module contains a registry that remembers the file cache keys of uploaded files, so the same
+//! content is only uploaded again once its key has expired or ZeptoMail no longer accepts it.
+//!
+//! Files are identified by the SHA-256 hash of their content. Keys are kept in a [`FileCacheStore`]:
+//! [`MemoryFileCacheStore`] for a single process, or [`JsonFileCacheStore`] to keep them across restarts.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+
+use crate::models::content;
+use crate::{
+    Attachment,
+    FileUploadRequest,
+    FileUploadResponse,
+    ZeptoErrorCode,
+    ZeptoMailClient,
+    ZeptoMailError,
+    FILE_CACHE_TTL,
+};
+
+//Keys are not reused during the last day before they expire, so they cannot expire between being
+//handed out and the email being sent.
+const DEFAULT_EXPIRY_MARGIN: Duration = Duration::from_secs(24 * 60 * 60);
+
+//The buffer size files are hashed with.
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// A file cache key the registry remembers for some content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedFile {
+    pub file_cache_key: String,
+    pub content_type: String,
+    pub expires_at: SystemTime,
+}
+
+/// Where a [`FileCacheRegistry`] keeps file cache keys, by the SHA-256 hash of the content.
+#[async_trait]
+pub trait FileCacheStore: Send + Sync {
+    async fn get(&self, hash: &str) -> Result<Option<CachedFile>, ZeptoMailError>;
+
+    async fn put(&self, hash: &str, cached_file: CachedFile) -> Result<(), ZeptoMailError>;
+
+    async fn remove(&self, hash: &str) -> Result<(), ZeptoMailError>;
+}
+
+/// A [`FileCacheStore`] that keeps keys in memory. Clones share the same keys.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFileCacheStore {
+    entries: Arc<Mutex<HashMap<String, CachedFile>>>,
+}
+
+impl MemoryFileCacheStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, CachedFile>> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl FileCacheStore for MemoryFileCacheStore {
+    async fn get(&self, hash: &str) -> Result<Option<CachedFile>, ZeptoMailError> {
+        Ok(self.entries().get(hash).cloned())
+    }
+
+    async fn put(&self, hash: &str, cached_file: CachedFile) -> Result<(), ZeptoMailError> {
+        self.entries().insert(hash.to_string(), cached_file);
+        Ok(())
+    }
+
+    async fn remove(&self, hash: &str) -> Result<(), ZeptoMailError> {
+        self.entries().remove(hash);
+        Ok(())
+    }
+}
+
+/// A [`FileCacheStore`] that keeps keys in a JSON file, so they survive restarts.
+///
+/// The file is rewritten on every change, without the keys that have expired. It should not be
+/// shared by several processes at the same time.
+#[derive(Debug)]
+pub struct JsonFileCacheStore {
+    path: PathBuf,
+    entries: tokio::sync::Mutex<HashMap<String, CachedFile>>,
+}
+
+impl JsonFileCacheStore {
+    /// Loads the keys from the JSON file at `path`, which is created on the first change if it
+    /// does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If the file exists but cannot be read.
+    /// * `ZeptoMailError::SerializationError` - If the file is not a valid store.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, ZeptoMailError> {
+        let path: PathBuf = path.into();
+        let entries: HashMap<String, CachedFile> = match tokio::fs::read(&path).await {
+            Ok(json) => serde_json::from_slice(&json)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(ZeptoMailError::IoError(err)),
+        };
+
+        Ok(JsonFileCacheStore {
+            path,
+            entries: tokio::sync::Mutex::new(entries),
+        })
+    }
+
+    //Writes to a temporary file first, so a crash cannot leave a truncated store behind.
+    async fn save(&self, entries: &mut HashMap<String, CachedFile>) -> Result<(), ZeptoMailError> {
+        let now: SystemTime = SystemTime::now();
+        entries.retain(|_, cached_file| cached_file.expires_at > now);
+
+        let json: Vec<u8> = serde_json::to_vec_pretty(entries)?;
+        let mut temporary_path: std::ffi::OsString = self.path.clone().into_os_string();
+        temporary_path.push(".tmp");
+
+        tokio::fs::write(&temporary_path, json).await.map_err(ZeptoMailError::IoError)?;
+        tokio::fs::rename(&temporary_path, &self.path).await.map_err(ZeptoMailError::IoError)
+    }
+}
+
+#[async_trait]
+impl FileCacheStore for JsonFileCacheStore {
+    async fn get(&self, hash: &str) -> Result<Option<CachedFile>, ZeptoMailError> {
+        Ok(self.entries.lock().await.get(hash).cloned())
+    }
+
+    async fn put(&self, hash: &str, cached_file: CachedFile) -> Result<(), ZeptoMailError> {
+        let mut entries = self.entries.lock().await;
+        entries.insert(hash.to_string(), cached_file);
+        self.save(&mut entries).await
+    }
+
+    async fn remove(&self, hash: &str) -> Result<(), ZeptoMailError> {
+        let mut entries = self.entries.lock().await;
+        if entries.remove(hash).is_some() {
+            self.save(&mut entries).await?;
+        }
+        Ok(())
+    }
+}
+
+/// The content of a file to attach through a [`FileCacheRegistry`].
+#[derive(Debug, Clone)]
+pub enum FileSource {
+    Bytes {
+        name: String,
+        content_type: String,
+        data: Bytes,
+    },
+    //Read when it is hashed and, if needed, streamed when it is uploaded.
+    Path(PathBuf),
+}
+
+impl FileSource {
+    /// In-memory content, with the MIME type inferred from the content and the extension of `name`.
+    pub fn bytes(name: impl Into<String>, data: impl Into<Bytes>) -> Self {
+        let name: String = name.into();
+        let data: Bytes = data.into();
+        FileSource::Bytes {
+            content_type: content::detect_mime_type(&name, &data),
+            name,
+            data,
+        }
+    }
+
+    /// The file at `path`, attached under its file name.
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        FileSource::Path(path.into())
+    }
+
+    fn name(&self) -> Result<String, ZeptoMailError> {
+        match self {
+            FileSource::Bytes { name, .. } => Ok(name.clone()),
+            FileSource::Path(path) => content::file_name(path),
+        }
+    }
+
+    async fn hash(&self) -> Result<String, ZeptoMailError> {
+        let digest = match self {
+            FileSource::Bytes { data, .. } => Sha256::digest(data),
+            FileSource::Path(path) => hash_file(path).await?,
+        };
+        Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+
+    async fn upload_request(&self) -> Result<FileUploadRequest, ZeptoMailError> {
+        match self {
+            FileSource::Bytes { name, content_type, data } => {
+                Ok(FileUploadRequest::new(name.clone(), content_type.clone(), data.clone()))
+            }
+            FileSource::Path(path) => FileUploadRequest::from_path(path).await,
+        }
+    }
+}
+
+/// Remembers the file cache keys of uploaded files, so that attaching the same content again
+/// reuses the key instead of uploading the file again.
+///
+/// Keys are reused until shortly before they expire, [`FILE_CACHE_TTL`] after the upload. Should
+/// ZeptoMail reject a key earlier, [`send_with_attachments`](Self::send_with_attachments) uploads
+/// the files again and retries the send once.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use zeptomail_rs::{ZeptoMailClient, Region, EmailRequest};
+/// use zeptomail_rs::file_cache::registry::{FileCacheRegistry, FileSource, JsonFileCacheStore};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+///     let store = JsonFileCacheStore::open("file-cache-keys.json").await?;
+///     let registry = FileCacheRegistry::new(client.clone(), store);
+///
+///     let terms = FileSource::path("terms-and-conditions.pdf");
+///     let response = registry.send_with_attachments(&[terms], |attachments| {
+///         let client = client.clone();
+///         async move {
+///             let mut builder = EmailRequest::builder()
+///                 .sender("sender@example.com")
+///                 .to("recipient@example.com")
+///                 .subject("Your order")
+///                 .html("<p>Thank you for your order.</p>");
+///             for attachment in attachments {
+///                 builder = builder.attach(attachment);
+///             }
+///             client.send_email(builder.build()?).await
+///         }
+///     }).await?;
+///
+///     println!("{:?}", response);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct FileCacheRegistry {
+    client: ZeptoMailClient,
+    store: Arc<dyn FileCacheStore>,
+    ttl: Duration,
+    expiry_margin: Duration,
+}
+
+impl FileCacheRegistry {
+    pub fn new(client: ZeptoMailClient, store: impl FileCacheStore + 'static) -> Self {
+        FileCacheRegistry {
+            client,
+            store: Arc::new(store),
+            ttl: FILE_CACHE_TTL,
+            expiry_margin: DEFAULT_EXPIRY_MARGIN,
+        }
+    }
+
+    /// Sets how long ZeptoMail keeps uploaded files. Defaults to [`FILE_CACHE_TTL`].
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long before their expiry keys stop being reused. Defaults to one day.
+    pub fn expiry_margin(mut self, expiry_margin: Duration) -> Self {
+        self.expiry_margin = expiry_margin;
+        self
+    }
+
+    /// Returns an attachment for `file`, uploading it only if there is no usable key for its content.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If the file cannot be read.
+    /// * Any error of [`ZeptoMailClient::upload_file_to_cache`] or of the store.
+    pub async fn attachment(&self, file: &FileSource) -> Result<Attachment, ZeptoMailError> {
+        let hash: String = file.hash().await?;
+        let name: String = file.name()?;
+
+        if let Some(cached_file) = self.store.get(&hash).await? {
+            if cached_file.expires_at > SystemTime::now() + self.expiry_margin {
+                return Ok(cached_attachment(name, cached_file));
+            }
+        }
+
+        let cached_file: CachedFile = self.upload(file).await?;
+        self.store.put(&hash, cached_file.clone()).await?;
+        Ok(cached_attachment(name, cached_file))
+    }
+
+    /// Forgets the key for `file`, so the next [`attachment`](Self::attachment) uploads it again.
+    pub async fn invalidate(&self, file: &FileSource) -> Result<(), ZeptoMailError> {
+        self.store.remove(&file.hash().await?).await
+    }
+
+    /// Calls `send` with attachments for `files`. If ZeptoMail rejects the request because of an
+    /// attachment, e.g. because a key expired early, the files are uploaded again and `send` is
+    /// called once more with the new keys.
+    ///
+    /// # Errors
+    ///
+    /// * The error returned by `send`, or any error of [`attachment`](Self::attachment).
+    pub async fn send_with_attachments<F, Fut, T>(
+        &self,
+        files: &[FileSource],
+        send: F
+    ) -> Result<T, ZeptoMailError>
+    where
+        F: Fn(Vec<Attachment>) -> Fut,
+        Fut: Future<Output = Result<T, ZeptoMailError>>,
+    {
+        let attachments: Vec<Attachment> = self.attachments(files).await?;
+        match send(attachments).await {
+            Err(error) if is_attachment_rejection(&error) => {
+                for file in files {
+                    self.invalidate(file).await?;
+                }
+                send(self.attachments(files).await?).await
+            }
+            result => result,
+        }
+    }
+
+    async fn attachments(&self, files: &[FileSource]) -> Result<Vec<Attachment>, ZeptoMailError> {
+        let mut attachments: Vec<Attachment> = Vec::with_capacity(files.len());
+        for file in files {
+            attachments.push(self.attachment(file).await?);
+        }
+        Ok(attachments)
+    }
+
+    async fn upload(&self, file: &FileSource) -> Result<CachedFile, ZeptoMailError> {
+        let file_upload_request: FileUploadRequest = file.upload_request().await?;
+        let content_type: String = file_upload_request.content_type.clone();
+        let response: FileUploadResponse = self.client.upload_file_to_cache(file_upload_request).await?;
+
+        Ok(CachedFile {
+            file_cache_key: response.file_cache_key,
+            content_type,
+            expires_at: SystemTime::now() + self.ttl,
+        })
+    }
+}
+
+fn cached_attachment(name: String, cached_file: CachedFile) -> Attachment {
+    let mut attachment: Attachment = Attachment::from_file_cache(name, cached_file.file_cache_key);
+    attachment.mime_type = Some(cached_file.content_type);
+    attachment
+}
+
+async fn hash_file(path: &Path) -> Result<sha2::digest::Output<Sha256>, ZeptoMailError> {
+    let mut file: tokio::fs::File = tokio::fs::File::open(path).await.map_err(ZeptoMailError::IoError)?;
+    let mut hasher: Sha256 = Sha256::new();
+    let mut buffer: Vec<u8> = vec![0; HASH_BUFFER_SIZE];
+    loop {
+        let read: usize = file.read(&mut buffer).await.map_err(ZeptoMailError::IoError)?;
+        if read == 0 {
+            return Ok(hasher.finalize());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+//Whether ZeptoMail rejected a request because of an attachment, e.g. an unknown file cache key.
+fn is_attachment_rejection(error: &ZeptoMailError) -> bool {
+    let ZeptoMailError::ApiError(api_error) = error.last_error() else {
+        return false;
+    };
+
+    api_error.code == ZeptoErrorCode::InvalidAttachment
+        || api_error.details.iter().flatten().any(|detail| {
+            detail.code == ZeptoErrorCode::InvalidAttachment
+                || detail.target.as_deref().is_some_and(|target| {
+                    target.contains("file_cache_key") || target.contains("attachments")
+                })
+        })
+}
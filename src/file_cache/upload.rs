@@ -1,56 +1,70 @@
 //! ## File Upload to Cache
 //! This module contains the implementation for uploading files to the cache using the ZeptoMail API.
 
-use reqwest::multipart::{Form, Part};
-use reqwest::{Response, StatusCode};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::SystemTime;
 
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use reqwest::header::HeaderValue;
+use reqwest::Method;
+use tokio_util::io::ReaderStream;
+
+use crate::models::file_cache::{ProgressCallback, UploadSource, FILE_CACHE_TTL};
+use crate::request::{BaseUrl, Endpoint, Payload};
+use crate::transport::BodyStream;
+use crate::validation::{Validate, ValidationErrors, ValidationRules, ViolationKind};
 use crate::{
     ZeptoMailClient,
     FileUploadRequest,
     FileUploadResponse,
+    UploadProgress,
     ZeptoMailError,
-    ApiError,
 };
 
+//The chunk size in-memory content is sent in when progress is reported.
+const PROGRESS_CHUNK_SIZE: usize = 64 * 1024;
+
 impl ZeptoMailClient {
     /// Uploads a file to the cache using the ZeptoMail API.
     ///
-    /// This function uploads a file to the ZeptoMail cache. It constructs a multipart form with the file data
-    /// and sends the request to the API.
+    /// This function sends the file content as the raw request body, with the file name as the `name`
+    /// query parameter and the MIME type as the Content-Type header. Files and streams are read while
+    /// the request is sent, without loading them into memory.
     ///
     /// # Arguments
     ///
-    /// * `file_upload_request` - A `FileUploadRequest` struct containing the details of the file to be uploaded, 
-    ///   including the file name, content type, and file data.
+    /// * `file_upload_request` - A `FileUploadRequest` with the name, content type and content of the file.
     ///
     /// # Returns
     ///
     /// This function returns a `Result`:
-    /// * `Ok(FileUploadResponse)` - If the file is successfully uploaded, containing the API response.
+    /// * `Ok(FileUploadResponse)` - If the file is successfully uploaded, containing the file cache key and its expiry.
     /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
     ///
     /// # Errors
     ///
     /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the name is missing or blocked, or the content type is invalid.
     /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
     /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
-    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing or deserializing the request/response.
+    /// * `ZeptoMailError::IoError` - If the file cannot be read.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use zeptomail_rs::{ZeptoMailClient,FileUploadRequest, FileUploadResponse};
+    /// use zeptomail_rs::{ZeptoMailClient, Region, FileUploadRequest};
     /// use std::error::Error;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let client = ZeptoMailClient::new("your_api_key")?;
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
     ///
-    ///     let file_upload_request = FileUploadRequest {
-    ///         name: "example.txt".to_string(),
-    ///         content_type: "text/plain".to_string(),
-    ///         data: b"Hello, world!".to_vec(),
-    ///     };
+    ///     let file_upload_request = FileUploadRequest::new("example.txt", "text/plain", "Hello, world!");
     ///
     ///     match client.upload_file_to_cache(file_upload_request).await {
     ///         Ok(response) => println!("File uploaded successfully: {:?}", response),
@@ -64,32 +78,118 @@ impl ZeptoMailClient {
         &self,
         file_upload_request: FileUploadRequest
     ) -> Result<FileUploadResponse, ZeptoMailError> {
-        let endpoint: &str = "files";
-        let url: String = format!("{}/{}", self.base_url, endpoint);
-
-        // Create the multipart form with the file data
-        let form = Form::new()
-            .text("name", file_upload_request.name)
-            .text("content_type", file_upload_request.content_type)
-            .part("data", Part::bytes(file_upload_request.data).file_name("upload"));
-
-        let response: Response = self.client
-            .post(&url)
-            .header("Authorization", format!("Zoho-enczapikey {}", self.api_key))
-            .multipart(form)
-            .send()
-            .await?;
-
-        let status: StatusCode = response.status();
-
-        if status.is_success() {
-            let response_text: String = response.text().await?;
-            let success_response: FileUploadResponse = serde_json::from_str(&response_text)?;
-            Ok(success_response)
-        } else {
-            let response_text: String = response.text().await?;
-            let api_error: ApiError = serde_json::from_str(&response_text)?;
-            Err(ZeptoMailError::ApiError(api_error))
+        let mut response: FileUploadResponse = self.execute(&file_upload_request).await?;
+        response.expires_at = Some(SystemTime::now() + FILE_CACHE_TTL);
+        Ok(response)
+    }
+}
+
+impl Endpoint for FileUploadRequest {
+    type Response = FileUploadResponse;
+
+    const METHOD: Method = Method::POST;
+
+    const BASE_URL: BaseUrl = BaseUrl::Upload;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["files"]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![("name", self.name.clone())]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        let content_type: HeaderValue = HeaderValue::from_str(&self.content_type).map_err(|_| {
+            let kind: ViolationKind = ViolationKind::InvalidContentType(self.content_type.clone());
+            ZeptoMailError::Validation(ValidationErrors::single("content_type", kind))
+        })?;
+
+        let (stream, length): (BodyStream, u64) = match &self.source {
+            UploadSource::Bytes(data) if self.progress.is_none() => {
+                return Ok(Payload::Raw { content_type, body: data.clone() });
+            }
+            UploadSource::Bytes(data) => {
+                //Sent in chunks so that progress is reported while the body is sent.
+                let chunks: Vec<Result<Bytes, io::Error>> = (0..data.len())
+                    .step_by(PROGRESS_CHUNK_SIZE)
+                    .map(|start| Ok(data.slice(start..data.len().min(start + PROGRESS_CHUNK_SIZE))))
+                    .collect();
+                (Box::pin(futures_util::stream::iter(chunks)), data.len() as u64)
+            }
+            UploadSource::Path { path, length } => {
+                let stream: BodyStream = Box::pin(
+                    futures_util::stream::once(tokio::fs::File::open(path.clone()))
+                        .map_ok(ReaderStream::new)
+                        .try_flatten(),
+                );
+                (stream, *length)
+            }
+            UploadSource::Stream { stream, length } => {
+                let stream: BodyStream = stream
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .take()
+                    .ok_or_else(|| ZeptoMailError::IoError(io::Error::other("the upload stream was already read")))?;
+                (stream, *length)
+            }
+        };
+
+        let stream: BodyStream = Box::pin(SizedStream {
+            inner: stream,
+            bytes_sent: 0,
+            total_bytes: length,
+            progress: self.progress.clone(),
+        });
+        Ok(Payload::Stream { content_type, stream, length })
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        self.validate_with(rules)
+    }
+
+    fn is_replayable(&self) -> bool {
+        !matches!(self.source, UploadSource::Stream { .. })
+    }
+}
+
+//Reports progress for the body stream and makes sure it yields exactly the announced Content-Length,
+//so a wrong length fails the upload instead of sending a truncated file.
+struct SizedStream {
+    inner: BodyStream,
+    bytes_sent: u64,
+    total_bytes: u64,
+    progress: Option<ProgressCallback>,
+}
+
+impl Stream for SizedStream {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let chunk: Bytes = match self.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => chunk,
+            Poll::Ready(None) if self.bytes_sent < self.total_bytes => {
+                let message: String = format!(
+                    "the upload ended after {} of {} bytes",
+                    self.bytes_sent, self.total_bytes
+                );
+                return Poll::Ready(Some(Err(io::Error::new(io::ErrorKind::UnexpectedEof, message))));
+            }
+            other => return other,
+        };
+
+        self.bytes_sent += chunk.len() as u64;
+        if self.bytes_sent > self.total_bytes {
+            let message: String = format!("the upload is longer than the announced {} bytes", self.total_bytes);
+            return Poll::Ready(Some(Err(io::Error::new(io::ErrorKind::InvalidData, message))));
+        }
+
+        if let Some(progress) = &self.progress {
+            progress(UploadProgress {
+                bytes_sent: self.bytes_sent,
+                total_bytes: self.total_bytes,
+            });
         }
+        Poll::Ready(Some(Ok(chunk)))
     }
-}
\ No newline at end of file
+}
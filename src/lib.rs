@@ -8,29 +8,57 @@
 //! - [**`Send Email with Template`**](#send-email-with-template): An abstraction for operations over the [ZeptoMail Templates API - Single Email](https://www.zoho.com/zeptomail/help/api/email-templates.html)
 //! - [**`Send Batch Email with Template`**](#send-batch-email-with-template): An abstraction for operations over the [ZeptoMail Templates API - Batch Email](https://www.zoho.com/zeptomail/help/api/batch-email-templates.html)
 //! - [**File Upload to Cache**](#file-upload-to-cache): An abstraction for operations over the [ZeptoMail File Cache Upload API](https://www.zoho.com/zeptomail/help/api/file-upload.html)
+//! - [**Template Management**](./templates/manage/index.html): List, fetch, create, update and delete the templates of a Mail Agent
+//! - [**Suppression List**](./suppression/list/index.html): List, add and remove suppressed addresses and domains
+//! - [**Suppression Guard**](./suppression/guard/index.html): Drop suppressed recipients, or reject the send, before it leaves the client
+//! - [**Webhooks**](./webhooks/index.html): Typed events for the bounce, open, click, spam complaint and delivery notifications ZeptoMail sends to webhooks, with ready-made `axum` and `actix-web` routes behind the features of the same name
 //! 
 //! ## Usage
 //! 
-//! First make sure you initialize the client with your API key:
+//! First make sure you initialize the client with your API key and the [`Region`] your account is hosted in:
 //! 
-//! ```rust
-//! let client = ZeptoMailClient::new("your_api_key");
+//! ```rust,no_run
+//! use zeptomail_rs::{ZeptoMailClient, Region};
+//! 
+//! let client = ZeptoMailClient::new("your_api_key", Region::Eu).unwrap();
+//! ```
+//! 
+//! For proxies and test servers, the base URL can be overridden with [`ZeptoMailClient::with_base_url`].
+//! Timeouts, proxies, root certificates, the user agent and default headers can be configured with
+//! [`ZeptoMailClient::builder`], which also accepts a pre-built `reqwest::Client`:
+//! 
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use zeptomail_rs::{ZeptoMailClient, Region};
+//! 
+//! let client = ZeptoMailClient::builder("your_api_key", Region::Us)
+//!     .timeout(Duration::from_secs(10))
+//!     .build()
+//!     .unwrap();
 //! ```
 //! 
+//! Requests are sent through a [`Transport`](transport::Transport). The default is backed by `reqwest`;
+//! [`MemoryTransport`](transport::MemoryTransport) answers with queued responses so applications can be
+//! tested without the network, and custom transports can be plugged in with
+//! [`ZeptoMailClientBuilder::transport`].
+//! 
 //! ### Send Email
 //! 
-//! ```rust
-//! use zeptomail_sdk::{ZeptoMailClient, EmailRequest};
+//! Requests are put together with builders, which run the checks in [`validation`] on the result:
+//! 
+//! ```rust,no_run
+//! use zeptomail_rs::{ZeptoMailClient, Region, EmailRequest, EmailAddress};
 //! 
 //! #[tokio::main]
 //! async fn main() {
-//!     let client = ZeptoMailClient::new("your_api_key");
-//!     let email_request = EmailRequest {
-//!         from: "sender@example.com".to_string(),
-//!         to: vec!["recipient@example.com".to_string()],
-//!         subject: "Test Email".to_string(),
-//!         content: "This is a test email.".to_string(),
-//!     };
+//!     let client = ZeptoMailClient::new("your_api_key", Region::Eu).unwrap();
+//!     let email_request = EmailRequest::builder()
+//!         .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+//!         .to("recipient@example.com")
+//!         .subject("Test Email")
+//!         .html("<div>This is a test email.</div>")
+//!         .build()
+//!         .unwrap();
 //! 
 //!     match client.send_email(email_request).await {
 //!         Ok(response) => println!("Email sent successfully: {:?}", response),
@@ -41,51 +69,24 @@
 //! 
 //! ### Send Batch Email
 //! 
-//! ```rust
-//! use zeptomail_sdk::{ZeptoMailClient, BatchEmailRequest, EmailAddress, Recipient};
+//! ```rust,no_run
+//! use zeptomail_rs::{ZeptoMailClient, Region, BatchEmailRequest, EmailAddress, Recipient};
 //! use std::error::Error;
 //! 
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn Error>> {
-//!     let client = ZeptoMailClient::new("your_api_key");
-//! 
-//!     let sender = EmailAddress {
-//!         address: "sender@example.com".to_string(),
-//!         name: Some("Sender Name".to_string()),
-//!     };
-//! 
-//!     let recipients = vec![
-//!         Recipient {
-//!             email_address: EmailAddress {
-//!                 address: "recipient1@example.com".to_string(),
-//!                 name: Some("Recipient One".to_string()),
-//!             },
-//!             merge_info: None,
-//!         },
-//!         Recipient {
-//!             email_address: EmailAddress {
-//!                 address: "recipient2@example.com".to_string(),
-//!                 name: Some("Recipient Two".to_string()),
-//!             },
-//!             merge_info: None,
-//!         },
-//!     ];
-//! 
-//!     let batch_email_request = BatchEmailRequest {
-//!         sender,
-//!         recipients,
-//!         subject: "Batch Email Test".to_string(),
-//!         htmlbody: Some("<div>Batch Email Content</div>".to_string()),
-//!         textbody: Some("Batch Email Content".to_string()),
-//!         carbon_copy: None,
-//!         blind_carbon_copy: None,
-//!         track_clicks: Some(true),
-//!         track_opens: Some(true),
-//!         client_reference: None,
-//!         mime_headers: None,
-//!         attachments: None,
-//!         inline_images: None,
-//!     };
+//!     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+//! 
+//!     let batch_email_request = BatchEmailRequest::builder()
+//!         .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+//!         .to(Recipient::new("recipient1@example.com").with_name("Recipient One"))
+//!         .to(Recipient::new("recipient2@example.com").with_name("Recipient Two"))
+//!         .subject("Batch Email Test")
+//!         .html("<div>Batch Email Content</div>")
+//!         .text("Batch Email Content")
+//!         .track_clicks(true)
+//!         .track_opens(true)
+//!         .build()?;
 //! 
 //!     match client.send_batch_email(batch_email_request).await {
 //!         Ok(response) => println!("Email sent successfully: {:?}", response),
@@ -98,27 +99,23 @@
 //!
 //! ### Send Email with Template
 //! 
-//! ```rust
-//! use zeptomail_sdk::{ZeptoMailClient, TemplateEmailRequest, EmailAddress, Recipient};
+//! ```rust,no_run
+//! use zeptomail_rs::{ZeptoMailClient, Region, TemplateEmailRequest};
 //! use std::error::Error;
 //! 
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn Error>> {
-//!     let client = ZeptoMailClient::new("your_api_key");
-//! 
-//!     let template_email_request = TemplateEmailRequest {
-//!         template_key: "your_template_key".to_string(),
-//!         bounce_address: Some("bounce@example.com".to_string()),
-//!         sender: EmailAddress::new("sender@example.com".to_string()),
-//!         recipients: vec![Recipient::new("recipient@example.com".to_string())],
-//!         reply_to: Some(vec![EmailAddress::new("replyto@example.com".to_string())]),
-//!         track_clicks: Some(true),
-//!         track_opens: Some(true),
-//!         client_reference: Some("client_ref".to_string()),
-//!         mime_headers: None,
-//!         attachments: None,
-//!         merge_info: Some(std::collections::HashMap::new()),
-//!     };
+//!     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+//! 
+//!     let template_email_request = TemplateEmailRequest::builder("your_template_key")
+//!         .bounce_address("bounce@example.com")
+//!         .sender("sender@example.com")
+//!         .to("recipient@example.com")
+//!         .track_clicks(true)
+//!         .track_opens(true)
+//!         .client_reference("client_ref")
+//!         .merge_field("name", "Recipient")
+//!         .build()?;
 //! 
 //!     match client.send_template_email(template_email_request).await {
 //!         Ok(response) => println!("Email sent successfully: {:?}", response),
@@ -130,29 +127,23 @@
 //! ```
 //! ### Send Batch Email with Template
 //! 
-//! ```rust
-//! use zeptomail_sdk::{ZeptoMailClient, BatchTemplateEmailRequest, EmailAddress, Recipient};
+//! ```rust,no_run
+//! use zeptomail_rs::{ZeptoMailClient, Region, BatchTemplateEmailRequest, Recipient};
 //! use std::error::Error;
 //! 
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn Error>> {
-//!     let client = ZeptoMailClient::new("your_api_key");
-//! 
-//!     let batch_template_email_request = BatchTemplateEmailRequest {
-//!         template_key: "your_template_key".to_string(),
-//!         bounce_address: Some("bounce@example.com".to_string()),
-//!         sender: EmailAddress::new("sender@example.com".to_string()),
-//!         recipients: vec![
-//!             Recipient::new("recipient1@example.com".to_string()),
-//!             Recipient::new("recipient2@example.com".to_string())
-//!         ],
-//!         reply_to: Some(vec![EmailAddress::new("replyto@example.com".to_string())]),
-//!         track_clicks: Some(true),
-//!         track_opens: Some(true),
-//!         client_reference: Some("client_ref".to_string()),
-//!         mime_headers: None,
-//!         attachments: None,
-//!     };
+//!     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+//! 
+//!     let batch_template_email_request = BatchTemplateEmailRequest::builder("your_template_key")
+//!         .bounce_address("bounce@example.com")
+//!         .sender("sender@example.com")
+//!         .to(Recipient::new("recipient1@example.com").with_merge_field("name", "Recipient One"))
+//!         .to(Recipient::new("recipient2@example.com").with_merge_field("name", "Recipient Two"))
+//!         .track_clicks(true)
+//!         .track_opens(true)
+//!         .client_reference("client_ref")
+//!         .build()?;
 //! 
 //!     match client.send_batch_template_email(batch_template_email_request).await {
 //!         Ok(response) => println!("Batch email sent successfully: {:?}", response),
@@ -164,19 +155,15 @@
 //! ```
 //! ### File Upload to Cache
 //! 
-//! ```rust
-//! use zeptomail_sdk::{ZeptoMailClient, FileUploadRequest};
+//! ```rust,no_run
+//! use zeptomail_rs::{ZeptoMailClient, Region, FileUploadRequest};
 //! use std::error::Error;
 //! 
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn Error>> {
-//!     let client = ZeptoMailClient::new("your_api_key");
+//!     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
 //! 
-//!     let file_upload_request = FileUploadRequest {
-//!         name: "example.txt".to_string(),
-//!         content_type: "text/plain".to_string(),
-//!         data: b"Hello, world!".to_vec(),
-//!     };
+//!     let file_upload_request = FileUploadRequest::from_path("example.txt").await?;
 //! 
 //!     match client.upload_file_to_cache(file_upload_request).await {
 //!         Ok(response) => println!("File uploaded successfully: {:?}", response),
@@ -198,21 +185,44 @@
 //! ## [Send Batch Email with Template](./templates/batch/index.html)
 //! 
 //! ## [File Upload to Cache](./file_cache/upload/index.html)
+//! 
+//! ## [Template Management](./templates/manage/index.html)
+//! 
+//! ## [Suppression List](./suppression/list/index.html)
 
 
+pub mod batch;
 pub mod email;
 pub mod file_cache;
 pub mod models;
 pub mod templates;
 pub mod client;
+pub mod rate_limit;
+pub mod region;
+pub mod render;
+pub mod retry;
+pub mod suppression;
+pub mod transport;
+pub mod validation;
+pub mod webhooks;
+mod request;
 
-pub use client::ZeptoMailClient;
+pub use client::{ZeptoMailClient, ZeptoMailClientBuilder};
+pub use rate_limit::{RateLimiter, RateLimitMode};
+pub use region::Region;
+pub use retry::RetryPolicy;
 pub use models::{
     api_failure::{ApiErrorDetail, ApiError, ZeptoMailError},
     api_success::{SuccessData, ApiResponse},
-    common::{EmailAddress, Attachment, MimeHeaders},
-    email::{EmailRequest, BatchEmailRequest, Recipient},
-    file_cache::{FileUploadRequest, FileUploadResponse},
-    template::{TemplateEmailRequest, BatchTemplateEmailRequest},
+    common::{EmailAddress, Attachment, BlockedExtensions, MimeHeaders},
+    content::MAX_MESSAGE_SIZE,
+    email::{EmailRequest, EmailRequestBuilder, BatchEmailRequest, BatchEmailRequestBuilder, Recipient},
+    error_code::ZeptoErrorCode,
+    merge::{MergeData, MergeInfo},
+    file_cache::{FileUploadRequest, FileUploadResponse, UploadProgress, FILE_CACHE_TTL},
+    managed_template::{Template, TemplateDefinition, TemplateListOptions, TemplatePage},
+    suppression::{Suppression, SuppressionListOptions, SuppressionPage, SuppressionReason, SuppressionType},
+    webhook::{WebhookEvent, EventKind},
+    template::{TemplateEmailRequest, TemplateEmailRequestBuilder, BatchTemplateEmailRequest, BatchTemplateEmailRequestBuilder},
     email::InlineImage, 
 };
\ No newline at end of file
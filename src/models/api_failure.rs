@@ -1,44 +1,268 @@
 use std::fmt;
 use std::error::Error as StdError;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use reqwest::Error as ReqwestError;
 use serde_json::Error as SerdeJsonError;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::validation::ValidationErrors;
+use crate::EmailAddress;
+use crate::ZeptoErrorCode;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiErrorDetail {
-    pub code: String,
+    pub code: ZeptoErrorCode,
     pub message: String,
     pub target: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiError {
-    pub code: String,
+    pub code: ZeptoErrorCode,
     pub message: String,
     pub details: Option<Vec<ApiErrorDetail>>,
     pub request_id: Option<String>,
+    //The HTTP status code the error was returned with. Not part of the response body.
+    #[serde(skip)]
+    pub http_status: Option<u16>,
+}
+
+//ZeptoMail wraps errors in an `error` object, but some endpoints return them at the top level.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ApiErrorBody {
+    Wrapped { error: ApiError },
+    Bare(ApiError),
+}
+
+impl ApiErrorBody {
+    pub(crate) fn into_inner(self) -> ApiError {
+        match self {
+            ApiErrorBody::Wrapped { error } => error,
+            ApiErrorBody::Bare(error) => error,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum ZeptoMailError {
     ApiError(ApiError),
+    //An error response that is not a ZeptoMail error, e.g. an HTML page from a gateway or an empty body.
+    HttpError {
+        status: StatusCode,
+        headers: HeaderMap,
+        body_snippet: String,
+    },
     NetworkError(ReqwestError),
+    //The request could not be serialized.
     SerializationError(SerdeJsonError),
+    //A success response could not be parsed.
+    DeserializationError {
+        status: StatusCode,
+        source: SerdeJsonError,
+        body_snippet: String,
+    },
     UnexpectedResponse(String),
+    //The request failed validation before it was sent.
+    Validation(ValidationErrors),
+    //An attachment's name has an extension ZeptoMail rejects.
+    BlockedAttachment { name: String, extension: String },
+    //Content for an attachment or inline image exceeds ZeptoMail's message size limit.
+    MessageTooLarge { size: usize, limit: usize },
+    //A file or reader could not be read.
+    IoError(std::io::Error),
+    //The client was configured with invalid settings, e.g. an API key that is not a valid header value.
+    InvalidConfiguration(String),
+    //The client-side rate limiter had no capacity left and is configured to fail fast.
+    RateLimited { retry_after: Duration },
+    //The response body exceeded the client's response size limit.
+    ResponseTooLarge { limit: usize },
+    //Raised by custom transports that fail without a `reqwest` error.
+    TransportError(Box<dyn StdError + Send + Sync>),
+    //The suppression store rejected the send, either by policy or because no recipient was left.
+    Suppressed { recipients: Vec<EmailAddress> },
+    //The request was retried and the last attempt failed with `source`.
+    Retried { attempts: u32, source: Box<ZeptoMailError> },
 }
 
 impl fmt::Display for ZeptoMailError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ZeptoMailError::ApiError(api_error) => write!(f, "API Error: {}", api_error.message),
+            ZeptoMailError::HttpError { status, body_snippet, .. } => {
+                if body_snippet.is_empty() {
+                    write!(f, "HTTP Error: {}", status)
+                } else {
+                    write!(f, "HTTP Error: {}: {}", status, body_snippet)
+                }
+            }
             ZeptoMailError::NetworkError(err) => write!(f, "Network Error: {}", err),
             ZeptoMailError::SerializationError(err) => write!(f, "Serialization Error: {}", err),
+            ZeptoMailError::DeserializationError { status, source, .. } => {
+                write!(f, "Deserialization Error: could not parse the {} response: {}", status, source)
+            }
             ZeptoMailError::UnexpectedResponse(msg) => write!(f, "Unexpected Response: {}", msg),
+            ZeptoMailError::Validation(errors) => write!(f, "Validation Error: {}", errors),
+            ZeptoMailError::BlockedAttachment { name, extension } => {
+                write!(f, "Blocked Attachment: {:?} has the blocked extension `.{}`", name, extension)
+            }
+            ZeptoMailError::MessageTooLarge { size, limit } => {
+                write!(f, "Message Too Large: {} bytes exceed the limit of {} bytes", size, limit)
+            }
+            ZeptoMailError::IoError(err) => write!(f, "IO Error: {}", err),
+            ZeptoMailError::InvalidConfiguration(msg) => write!(f, "Invalid Configuration: {}", msg),
+            ZeptoMailError::RateLimited { retry_after } => write!(f, "Rate Limited: capacity is available again in {:?}", retry_after),
+            ZeptoMailError::ResponseTooLarge { limit } => write!(f, "Response Too Large: the response body exceeded {} bytes", limit),
+            ZeptoMailError::TransportError(err) => write!(f, "Transport Error: {}", err),
+            ZeptoMailError::Suppressed { recipients } => {
+                let addresses: Vec<&str> = recipients.iter().map(|recipient| recipient.address.as_str()).collect();
+                write!(f, "Suppressed: {} must not be sent to", addresses.join(", "))
+            }
+            ZeptoMailError::Retried { attempts, source } => write!(f, "{} (after {} attempts)", source, attempts),
+        }
+    }
+}
+
+impl StdError for ZeptoMailError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ZeptoMailError::NetworkError(err) => Some(err),
+            ZeptoMailError::SerializationError(err) => Some(err),
+            ZeptoMailError::DeserializationError { source, .. } => Some(source),
+            ZeptoMailError::Validation(errors) => Some(errors),
+            ZeptoMailError::IoError(err) => Some(err),
+            ZeptoMailError::TransportError(err) => Some(err.as_ref()),
+            ZeptoMailError::Retried { source, .. } => Some(source.as_ref()),
+            _ => None,
         }
     }
 }
 
-impl StdError for ZeptoMailError {}
+//The longest part of a response body kept in an error.
+const BODY_SNIPPET_LEN: usize = 512;
+
+/// The start of a response body, for error messages.
+pub(crate) fn body_snippet(body: &[u8]) -> String {
+    let text = String::from_utf8_lossy(body);
+    let text: &str = text.trim();
+    match text.char_indices().nth(BODY_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+impl ApiError {
+    //The error's own code followed by the codes of its details.
+    fn codes(&self) -> impl Iterator<Item = &ZeptoErrorCode> {
+        std::iter::once(&self.code).chain(self.details.iter().flatten().map(|detail| &detail.code))
+    }
+}
+
+impl ZeptoMailError {
+    /// The number of attempts made before the error was returned, including the first one. This is
+    /// 1 unless the request was retried.
+    pub fn attempts(&self) -> u32 {
+        match self {
+            ZeptoMailError::Retried { attempts, .. } => *attempts,
+            _ => 1,
+        }
+    }
+
+    /// The error of the last attempt, without the [`Retried`](ZeptoMailError::Retried) wrapper.
+    pub fn last_error(&self) -> &ZeptoMailError {
+        match self {
+            ZeptoMailError::Retried { source, .. } => source,
+            error => error,
+        }
+    }
+
+    /// The API error code, if ZeptoMail returned an error response.
+    pub fn error_code(&self) -> Option<&ZeptoErrorCode> {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => Some(&api_error.code),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status code of the error response, if one was received.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => api_error.http_status.and_then(|status| StatusCode::from_u16(status).ok()),
+            ZeptoMailError::HttpError { status, .. } => Some(*status),
+            ZeptoMailError::NetworkError(err) => err.status(),
+            ZeptoMailError::DeserializationError { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The ID ZeptoMail assigned to the failed request, for support requests and log correlation.
+    pub fn request_id(&self) -> Option<&str> {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => api_error.request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the API key was rejected.
+    pub fn is_auth_failure(&self) -> bool {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => {
+                api_error.codes().any(ZeptoErrorCode::is_auth_failure)
+                    || api_error.http_status == Some(401)
+            }
+            ZeptoMailError::HttpError { status, .. } => *status == StatusCode::UNAUTHORIZED,
+            _ => false,
+        }
+    }
+
+    /// Whether a recipient address was rejected.
+    pub fn is_invalid_recipient(&self) -> bool {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => api_error.codes().any(ZeptoErrorCode::is_invalid_recipient),
+            _ => false,
+        }
+    }
+
+    /// Whether the request was throttled, by ZeptoMail or by the client-side rate limiter.
+    pub fn is_rate_limited(&self) -> bool {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => {
+                api_error.codes().any(ZeptoErrorCode::is_rate_limited)
+                    || api_error.http_status == Some(429)
+            }
+            ZeptoMailError::HttpError { status, .. } => *status == StatusCode::TOO_MANY_REQUESTS,
+            ZeptoMailError::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether retrying the request that caused this error may succeed.
+    ///
+    /// Throttling (HTTP 429), server errors (HTTP 5xx), timeouts and connection failures are
+    /// retryable, unless a known API error code says the request itself was rejected. Everything
+    /// else, including serialization errors, is fatal.
+    pub fn is_retryable(&self) -> bool {
+        match self.last_error() {
+            ZeptoMailError::ApiError(api_error) => {
+                if api_error.codes().any(ZeptoErrorCode::is_retryable) {
+                    return true;
+                }
+                if api_error.codes().any(ZeptoErrorCode::is_known) {
+                    return false;
+                }
+                matches!(api_error.http_status, Some(429) | Some(500..=599))
+            }
+            ZeptoMailError::HttpError { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            ZeptoMailError::NetworkError(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+            ZeptoMailError::TransportError(_) => true,
+            _ => false,
+        }
+    }
+}
 
 impl From<ReqwestError> for ZeptoMailError {
     fn from(error: ReqwestError) -> Self {
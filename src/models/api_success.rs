@@ -1,16 +1,24 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::EmailAddress;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuccessData {
     pub code: String,
     pub additional_info: Option<Vec<String>>,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse {
     pub data: Vec<SuccessData>,
     pub message: String,
     pub request_id: String,
     pub object: Option<String>, // Optional, since not all responses may have an object
+    //How many attempts it took to get this response, including the first one.
+    #[serde(skip)]
+    pub attempts: u32,
+    //The recipients, CCs and BCCs the suppression store dropped from the request.
+    #[serde(skip)]
+    pub suppressed: Vec<EmailAddress>,
 }
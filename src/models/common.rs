@@ -1,17 +1,138 @@
+use std::collections::HashSet;
+use std::path::Path;
+
 use serde::{Serialize, Deserialize};
+use tokio::io::AsyncRead;
+
+use crate::models::content;
+use crate::ZeptoMailError;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EmailAddress {
     pub address: String,
     pub name: Option<String>,
 }
 
-/*ZeptoMail does not support the following extensions as attachments:
-ade, adp, app, asp, bas, bat, cer, chm, cmd, com, cpl, crt, csh, der, exe, fxp, gadget, hlp, hpj, hta, inf, ins, 
-isp, js, jse, ksh, lib, lnk, mad, maf, mag, mam, maq, mar, mas, mat, mau, mav, maw, mda, mdb, mdt, mdw, mdz, msc, 
-msh, msh1, msh1xml, msh2, msh2xml, msi, msp, mst, ops, osd, pcd, plg, prf, prg, ps1, ps1xml, ps2, ps2xml, psc1, 
-psc2, pst, reg, scf, scr, sct, shb, shs, sys, tmp, url, vb, vbe, vbp, vbs, vsmacros, vsw, vxd, ws, wsc, wsf, wsh, xnk */
-#[derive(Debug, Serialize, Deserialize)]
+impl EmailAddress {
+    pub fn new(address: impl Into<String>) -> Self {
+        EmailAddress {
+            address: address.into(),
+            name: None,
+        }
+    }
+
+    /// Sets the display name, e.g. `"Jane Doe"` in `Jane Doe <jane@example.com>`.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl From<&str> for EmailAddress {
+    fn from(address: &str) -> Self {
+        EmailAddress::new(address)
+    }
+}
+
+impl From<String> for EmailAddress {
+    fn from(address: String) -> Self {
+        EmailAddress::new(address)
+    }
+}
+
+//ZeptoMail does not support the following extensions as attachments.
+const DEFAULT_BLOCKED_EXTENSIONS: [&str; 87] = [
+    "ade", "adp", "app", "asp", "bas", "bat", "cer", "chm", "cmd", "com", "cpl", "crt", "csh",
+    "der", "exe", "fxp", "gadget", "hlp", "hpj", "hta", "inf", "ins", "isp", "js", "jse", "ksh",
+    "lib", "lnk", "mad", "maf", "mag", "mam", "maq", "mar", "mas", "mat", "mau", "mav", "maw",
+    "mda", "mdb", "mdt", "mdw", "mdz", "msc", "msh", "msh1", "msh1xml", "msh2", "msh2xml", "msi",
+    "msp", "mst", "ops", "osd", "pcd", "plg", "prf", "prg", "ps1", "ps1xml", "ps2", "ps2xml",
+    "psc1", "psc2", "pst", "reg", "scf", "scr", "sct", "shb", "shs", "sys", "tmp", "url", "vb",
+    "vbe", "vbp", "vbs", "vsmacros", "vsw", "vxd", "ws", "wsc", "wsf", "wsh", "xnk",
+];
+
+/// The attachment extensions ZeptoMail rejects.
+///
+/// [`BlockedExtensions::default`] holds ZeptoMail's documented list. Should ZeptoMail change it,
+/// extensions can be added or removed, and the result passed to the client with
+/// [`ValidationRules::blocked_extensions`](crate::validation::ValidationRules::blocked_extensions).
+///
+/// Extensions are matched case-insensitively against the last extension of a file name, which is
+/// the one that decides how the file is opened. Double extensions like `invoice.pdf.exe` are caught,
+/// as are trailing dots and spaces that Windows ignores. Dots inside a name, as in
+/// `acme.com.invoice.pdf`, are not extensions.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::BlockedExtensions;
+///
+/// let blocked = BlockedExtensions::default().remove("tmp").insert("dmg");
+///
+/// assert_eq!(blocked.find("payload.EXE"), Some("exe"));
+/// assert_eq!(blocked.find("invoice.pdf.exe"), Some("exe"));
+/// assert_eq!(blocked.find("invoice.pdf.exe. "), Some("exe"));
+/// assert_eq!(blocked.find("installer.dmg"), Some("dmg"));
+/// assert_eq!(blocked.find("backup.tmp"), None);
+///
+/// assert_eq!(blocked.find("acme.com.invoice.pdf"), None);
+/// assert_eq!(blocked.find("my.app.screenshot.png"), None);
+/// assert_eq!(blocked.find("data.mat.csv"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedExtensions {
+    extensions: HashSet<String>,
+}
+
+impl BlockedExtensions {
+    /// A list without any blocked extensions.
+    pub fn empty() -> Self {
+        BlockedExtensions {
+            extensions: HashSet::new(),
+        }
+    }
+
+    /// Blocks `extension`, given with or without the leading dot.
+    pub fn insert(mut self, extension: impl AsRef<str>) -> Self {
+        self.extensions.insert(normalize_extension(extension.as_ref()));
+        self
+    }
+
+    /// Allows `extension` again, given with or without the leading dot.
+    pub fn remove(mut self, extension: impl AsRef<str>) -> Self {
+        self.extensions.remove(&normalize_extension(extension.as_ref()));
+        self
+    }
+
+    /// Whether `extension` is blocked.
+    pub fn contains(&self, extension: &str) -> bool {
+        self.extensions.contains(&normalize_extension(extension))
+    }
+
+    /// Returns the extension of `file_name`, in lowercase, if it is blocked.
+    pub fn find(&self, file_name: &str) -> Option<&str> {
+        //Windows ignores trailing dots and spaces, so `payload.exe. ` is still an executable.
+        let file_name: &str = file_name.trim_end_matches(['.', ' ']);
+        let file_name: &str = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name);
+
+        let (_, extension) = file_name.rsplit_once('.')?;
+        self.extensions.get(&normalize_extension(extension)).map(String::as_str)
+    }
+}
+
+impl Default for BlockedExtensions {
+    fn default() -> Self {
+        DEFAULT_BLOCKED_EXTENSIONS
+            .iter()
+            .fold(BlockedExtensions::empty(), |blocked, extension| blocked.insert(extension))
+    }
+}
+
+fn normalize_extension(extension: &str) -> String {
+    extension.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attachment {
     pub name: String,
     pub content: Option<String>,       // Base64 encoded content
@@ -19,8 +140,134 @@ pub struct Attachment {
     pub file_cache_key: Option<String>, // File cache key for an uploaded file
 }
 
+impl Attachment {
+    /// An attachment with base64 encoded `content`.
+    pub fn new(name: impl Into<String>, mime_type: impl Into<String>, content: impl Into<String>) -> Self {
+        Attachment {
+            name: name.into(),
+            content: Some(content.into()),
+            mime_type: Some(mime_type.into()),
+            file_cache_key: None,
+        }
+    }
+
+    /// An attachment with `bytes` as its content, base64 encoded, and the MIME type inferred from
+    /// the extension of `name`, or from the bytes if the extension is missing or unknown.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::MessageTooLarge` - If `bytes` exceed [`MAX_MESSAGE_SIZE`](crate::MAX_MESSAGE_SIZE).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zeptomail_rs::Attachment;
+    ///
+    /// let attachment = Attachment::from_bytes("report.pdf", b"%PDF-1.7 ...").unwrap();
+    ///
+    /// assert_eq!(attachment.mime_type.as_deref(), Some("application/pdf"));
+    /// assert_eq!(attachment.content.as_deref(), Some("JVBERi0xLjcgLi4u"));
+    /// ```
+    ///
+    /// Text that happens to start like a binary format keeps the type of its extension:
+    ///
+    /// ```rust
+    /// use zeptomail_rs::Attachment;
+    ///
+    /// let notes = Attachment::from_bytes("notes.txt", b"BMW order list\n").unwrap();
+    /// let data = Attachment::from_bytes("data.csv", b"II*\x00,foo").unwrap();
+    /// let unnamed = Attachment::from_bytes("scan", b"II*\x00...").unwrap();
+    ///
+    /// assert_eq!(notes.mime_type.as_deref(), Some("text/plain"));
+    /// assert_eq!(data.mime_type.as_deref(), Some("text/csv"));
+    /// assert_eq!(unnamed.mime_type.as_deref(), Some("image/tiff"));
+    /// ```
+    pub fn from_bytes(name: impl Into<String>, bytes: impl AsRef<[u8]>) -> Result<Self, ZeptoMailError> {
+        let name: String = name.into();
+        let bytes: &[u8] = bytes.as_ref();
+        Ok(Attachment {
+            mime_type: Some(content::detect_mime_type(&name, bytes)),
+            content: Some(content::encode(bytes)?),
+            name,
+            file_cache_key: None,
+        })
+    }
+
+    /// Reads the file at `path` into an attachment named after the file.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If the file cannot be read.
+    /// * `ZeptoMailError::MessageTooLarge` - If the file exceeds [`MAX_MESSAGE_SIZE`](crate::MAX_MESSAGE_SIZE).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::Attachment;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let attachment = Attachment::from_path("invoices/2024-08.pdf").await.unwrap();
+    ///     assert_eq!(attachment.name, "2024-08.pdf");
+    /// }
+    /// ```
+    pub async fn from_path(path: impl AsRef<Path>) -> Result<Self, ZeptoMailError> {
+        let path: &Path = path.as_ref();
+        let bytes: Vec<u8> = content::read_file(path).await?;
+        Attachment::from_bytes(content::file_name(path)?, bytes)
+    }
+
+    /// Reads `reader` to the end into an attachment named `name`.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If reading fails.
+    /// * `ZeptoMailError::MessageTooLarge` - If the reader yields more than [`MAX_MESSAGE_SIZE`](crate::MAX_MESSAGE_SIZE) bytes.
+    pub async fn from_reader<R>(name: impl Into<String>, reader: R) -> Result<Self, ZeptoMailError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let bytes: Vec<u8> = content::read_to_limit(reader).await?;
+        Attachment::from_bytes(name, bytes)
+    }
+
+    /// An attachment referring to a file uploaded with [`ZeptoMailClient::upload_file_to_cache`](crate::ZeptoMailClient::upload_file_to_cache).
+    pub fn from_file_cache(name: impl Into<String>, file_cache_key: impl Into<String>) -> Self {
+        Attachment {
+            name: name.into(),
+            content: None,
+            mime_type: None,
+            file_cache_key: Some(file_cache_key.into()),
+        }
+    }
+
+    /// Checks the name against ZeptoMail's [blocked extensions](BlockedExtensions).
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::BlockedAttachment` - If the name has a blocked extension.
+    pub fn check_extension(&self) -> Result<(), ZeptoMailError> {
+        self.check_extension_with(&BlockedExtensions::default())
+    }
+
+    /// Checks the name against a custom list of blocked extensions.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::BlockedAttachment` - If the name has a blocked extension.
+    pub fn check_extension_with(&self, blocked: &BlockedExtensions) -> Result<(), ZeptoMailError> {
+        match blocked.find(&self.name) {
+            Some(extension) => Err(ZeptoMailError::BlockedAttachment {
+                name: self.name.clone(),
+                extension: extension.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
 //The additional headers to be sent in the email for your reference purposes.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MimeHeaders {
     pub headers: std::collections::HashMap<String, String>,
 }
@@ -0,0 +1,98 @@
+//! ## Content
+//! This module contains the helpers that turn files, bytes and readers into the base64 encoded
+//! content of attachments and inline images.
+
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::ZeptoMailError;
+
+/// The largest email ZeptoMail accepts, including its body, attachments and inline images.
+pub const MAX_MESSAGE_SIZE: usize = 15 * 1024 * 1024;
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+//Signatures at the start of a file, and the MIME type they identify.
+const MAGIC_BYTES: [(&[u8], &str); 12] = [
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"BM", "image/bmp"),
+    (b"II*\x00", "image/tiff"),
+    (b"MM\x00*", "image/tiff"),
+    (b"\x00\x00\x01\x00", "image/x-icon"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"{\\rtf", "application/rtf"),
+];
+
+/// Base64 encodes `bytes` after checking them against [`MAX_MESSAGE_SIZE`].
+pub(crate) fn encode(bytes: &[u8]) -> Result<String, ZeptoMailError> {
+    check_size(bytes.len())?;
+    Ok(STANDARD.encode(bytes))
+}
+
+/// Infers the MIME type of a file from its name and first bytes.
+///
+/// A recognised extension wins. The bytes are only looked at when the extension is missing,
+/// unknown or only known as `application/octet-stream`, since the shorter signatures also start
+/// plenty of text files.
+pub(crate) fn detect_mime_type(name: &str, bytes: &[u8]) -> String {
+    match mime_guess::from_path(name).first_raw() {
+        Some(mime_type) if mime_type != OCTET_STREAM => mime_type.to_string(),
+        _ => sniff(bytes).unwrap_or(OCTET_STREAM).to_string(),
+    }
+}
+
+/// Reads a file, failing early if it is larger than [`MAX_MESSAGE_SIZE`].
+pub(crate) async fn read_file(path: &Path) -> Result<Vec<u8>, ZeptoMailError> {
+    let file: tokio::fs::File = tokio::fs::File::open(path).await.map_err(ZeptoMailError::IoError)?;
+    read_to_limit(file).await
+}
+
+/// Reads `reader` to the end, failing as soon as more than [`MAX_MESSAGE_SIZE`] bytes were read.
+pub(crate) async fn read_to_limit<R>(reader: R) -> Result<Vec<u8>, ZeptoMailError>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut bytes: Vec<u8> = Vec::new();
+    reader
+        .take(MAX_MESSAGE_SIZE as u64 + 1)
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(ZeptoMailError::IoError)?;
+    check_size(bytes.len())?;
+    Ok(bytes)
+}
+
+/// The file name of `path`, used as the attachment name.
+pub(crate) fn file_name(path: &Path) -> Result<String, ZeptoMailError> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| {
+            let message: String = format!("{} has no file name", path.display());
+            ZeptoMailError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidInput, message))
+        })
+}
+
+fn check_size(size: usize) -> Result<(), ZeptoMailError> {
+    if size > MAX_MESSAGE_SIZE {
+        return Err(ZeptoMailError::MessageTooLarge { size, limit: MAX_MESSAGE_SIZE });
+    }
+    Ok(())
+}
+
+fn sniff(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    MAGIC_BYTES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+        .map(|(_, mime_type)| *mime_type)
+}
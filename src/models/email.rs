@@ -1,36 +1,48 @@
-use std::collections::HashMap;
+use std::path::Path;
 use serde::{Serialize, Deserialize};
-use crate::{EmailAddress, Attachment, MimeHeaders};
+use serde_json::Value;
+use tokio::io::AsyncRead;
+use crate::models::content;
+use crate::validation::check_required_fields;
+use crate::{EmailAddress, Attachment, MergeData, MergeInfo, MimeHeaders, ZeptoMailError};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct EmailRequest {
     //A valid bounce email address as configured in your Mail Agent.
-    pub bounce_address: Option<String>, 
+    pub bounce_address: Option<String>,
     //A valid sender email address with "address" and "name" key-value pairs.
-    pub sender: EmailAddress,           
+    pub sender: EmailAddress,
     pub recipients: Vec<Recipient>,
     pub reply_to: Option<Vec<EmailAddress>>,
     //The subject of the email to be sent.
-    pub subject: String, 
+    pub subject: String,
     //The HTML body of the email to be sent.
-    pub htmlbody: Option<String>, 
+    pub htmlbody: Option<String>,
     pub textbody: Option<String>,
     pub carbon_copy: Option<Vec<Recipient>>,
     pub blind_carbon_copy: Option<Vec<Recipient>>,
     //You can also enable email click tracking in your Mail Agent under Email Tracking section.
-    //Note: The API setting will override the Mail Agent settings in your ZeptoMail account. 
-    pub track_clicks: Option<bool>, 
+    //Note: The API setting will override the Mail Agent settings in your ZeptoMail account.
+    pub track_clicks: Option<bool>,
     //You can also enable email open tracking in your Mail Agent under Email Tracking section.
-    //Note: The API setting will override the Mail Agent settings in your ZeptoMail account. 
+    //Note: The API setting will override the Mail Agent settings in your ZeptoMail account.
     pub track_opens: Option<bool>,
     //An identifier set by the user to track a particular transaction.
-    pub client_reference: Option<String>, 
-    pub mime_headers: Option<MimeHeaders>, 
+    pub client_reference: Option<String>,
+    pub mime_headers: Option<MimeHeaders>,
     pub attachments: Option<Vec<Attachment>>,
     pub inline_images: Option<Vec<InlineImage>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl EmailRequest {
+    pub fn builder() -> EmailRequestBuilder {
+        EmailRequestBuilder::default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BatchEmailRequest {
     pub sender: EmailAddress,
     pub recipients: Vec<Recipient>,
@@ -47,15 +59,427 @@ pub struct BatchEmailRequest {
     pub inline_images: Option<Vec<InlineImage>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl BatchEmailRequest {
+    pub fn builder() -> BatchEmailRequestBuilder {
+        BatchEmailRequestBuilder::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Recipient {
     pub email_address: EmailAddress,
-    pub merge_info: Option<HashMap<String, String>>,
+    pub merge_info: Option<MergeInfo>,
+}
+
+impl Recipient {
+    pub fn new(address: impl Into<String>) -> Self {
+        Recipient::from(EmailAddress::new(address))
+    }
+
+    /// Sets the recipient's display name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.email_address.name = Some(name.into());
+        self
+    }
+
+    /// Adds a value for the `{{key}}` merge field, personalizing batch and template emails.
+    pub fn with_merge_field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.merge_info
+            .get_or_insert_with(MergeInfo::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds the fields of `data`, e.g. a `#[derive(Serialize)]` struct, as merge fields. See [`MergeData`].
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::SerializationError` - If `data` does not serialize to a JSON object.
+    pub fn with_merge_data<T: MergeData + ?Sized>(mut self, data: &T) -> Result<Self, ZeptoMailError> {
+        self.merge_info.get_or_insert_with(MergeInfo::new).extend(data.to_merge_info()?);
+        Ok(self)
+    }
+}
+
+impl From<EmailAddress> for Recipient {
+    fn from(email_address: EmailAddress) -> Self {
+        Recipient {
+            email_address,
+            merge_info: None,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl From<&str> for Recipient {
+    fn from(address: &str) -> Self {
+        Recipient::new(address)
+    }
+}
+
+impl From<String> for Recipient {
+    fn from(address: String) -> Self {
+        Recipient::new(address)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InlineImage {
     pub mime_type: String,
     pub content: String,    // Base64 encoded content
     pub content_id: String,        // Content ID used in the HTML body
-}
\ No newline at end of file
+}
+
+impl InlineImage {
+    /// An inline image with base64 encoded `content`, referenced as `cid:<content_id>` in the HTML body.
+    pub fn new(content_id: impl Into<String>, mime_type: impl Into<String>, content: impl Into<String>) -> Self {
+        InlineImage {
+            mime_type: mime_type.into(),
+            content: content.into(),
+            content_id: content_id.into(),
+        }
+    }
+
+    /// An inline image with `bytes` as its content, base64 encoded, and the MIME type inferred
+    /// from the bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::MessageTooLarge` - If `bytes` exceed [`MAX_MESSAGE_SIZE`](crate::MAX_MESSAGE_SIZE).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zeptomail_rs::InlineImage;
+    ///
+    /// let logo = InlineImage::from_bytes("logo", b"\x89PNG\r\n\x1a\n...").unwrap();
+    ///
+    /// assert_eq!(logo.mime_type, "image/png");
+    /// ```
+    pub fn from_bytes(content_id: impl Into<String>, bytes: impl AsRef<[u8]>) -> Result<Self, ZeptoMailError> {
+        let bytes: &[u8] = bytes.as_ref();
+        Ok(InlineImage {
+            mime_type: content::detect_mime_type("", bytes),
+            content: content::encode(bytes)?,
+            content_id: content_id.into(),
+        })
+    }
+
+    /// Reads the image at `path`, inferring the MIME type from the bytes and the extension.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If the file cannot be read.
+    /// * `ZeptoMailError::MessageTooLarge` - If the file exceeds [`MAX_MESSAGE_SIZE`](crate::MAX_MESSAGE_SIZE).
+    pub async fn from_path(content_id: impl Into<String>, path: impl AsRef<Path>) -> Result<Self, ZeptoMailError> {
+        let path: &Path = path.as_ref();
+        let bytes: Vec<u8> = content::read_file(path).await?;
+        Ok(InlineImage {
+            mime_type: content::detect_mime_type(&content::file_name(path)?, &bytes),
+            content: content::encode(&bytes)?,
+            content_id: content_id.into(),
+        })
+    }
+
+    /// Reads `reader` to the end into an inline image.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If reading fails.
+    /// * `ZeptoMailError::MessageTooLarge` - If the reader yields more than [`MAX_MESSAGE_SIZE`](crate::MAX_MESSAGE_SIZE) bytes.
+    pub async fn from_reader<R>(content_id: impl Into<String>, reader: R) -> Result<Self, ZeptoMailError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let bytes: Vec<u8> = content::read_to_limit(reader).await?;
+        InlineImage::from_bytes(content_id, bytes)
+    }
+}
+
+/// Builder for an [`EmailRequest`].
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{EmailRequest, EmailAddress};
+///
+/// let email_request = EmailRequest::builder()
+///     .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+///     .to("recipient@example.com")
+///     .cc("manager@example.com")
+///     .subject("Test Email")
+///     .html("<div>Email Content</div>")
+///     .text("Email Content")
+///     .track_opens(true)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(email_request.recipients.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmailRequestBuilder {
+    bounce_address: Option<String>,
+    sender: Option<EmailAddress>,
+    recipients: Vec<Recipient>,
+    reply_to: Option<Vec<EmailAddress>>,
+    subject: Option<String>,
+    htmlbody: Option<String>,
+    textbody: Option<String>,
+    carbon_copy: Option<Vec<Recipient>>,
+    blind_carbon_copy: Option<Vec<Recipient>>,
+    track_clicks: Option<bool>,
+    track_opens: Option<bool>,
+    client_reference: Option<String>,
+    mime_headers: Option<MimeHeaders>,
+    attachments: Option<Vec<Attachment>>,
+    inline_images: Option<Vec<InlineImage>>,
+}
+
+impl EmailRequestBuilder {
+    pub fn bounce_address(mut self, address: impl Into<String>) -> Self {
+        self.bounce_address = Some(address.into());
+        self
+    }
+
+    pub fn sender(mut self, sender: impl Into<EmailAddress>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    pub fn to(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.recipients.push(recipient.into());
+        self
+    }
+
+    pub fn cc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn bcc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.blind_carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn reply_to(mut self, address: impl Into<EmailAddress>) -> Self {
+        self.reply_to.get_or_insert_with(Vec::new).push(address.into());
+        self
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    pub fn html(mut self, htmlbody: impl Into<String>) -> Self {
+        self.htmlbody = Some(htmlbody.into());
+        self
+    }
+
+    pub fn text(mut self, textbody: impl Into<String>) -> Self {
+        self.textbody = Some(textbody.into());
+        self
+    }
+
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    pub fn inline_image(mut self, inline_image: InlineImage) -> Self {
+        self.inline_images.get_or_insert_with(Vec::new).push(inline_image);
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.mime_headers
+            .get_or_insert_with(MimeHeaders::default)
+            .headers
+            .insert(name.into(), value.into());
+        self
+    }
+
+    pub fn track_clicks(mut self, track_clicks: bool) -> Self {
+        self.track_clicks = Some(track_clicks);
+        self
+    }
+
+    pub fn track_opens(mut self, track_opens: bool) -> Self {
+        self.track_opens = Some(track_opens);
+        self
+    }
+
+    pub fn client_reference(mut self, client_reference: impl Into<String>) -> Self {
+        self.client_reference = Some(client_reference.into());
+        self
+    }
+
+    /// Builds the request.
+    ///
+    /// Fails with `ZeptoMailError::Validation` if the sender or recipients are missing.
+    ///
+    /// Other problems, e.g. an invalid address or a blocked attachment, are checked by the client
+    /// against its [`ValidationRules`](crate::validation::ValidationRules) when the request is sent,
+    /// unless validation is turned off with
+    /// [`validate_requests`](crate::ZeptoMailClientBuilder::validate_requests). Call
+    /// [`Validate::validate`](crate::validation::Validate::validate) to check them earlier.
+    pub fn build(self) -> Result<EmailRequest, ZeptoMailError> {
+        check_required_fields(None, self.sender.as_ref(), &self.recipients).map_err(ZeptoMailError::Validation)?;
+
+        let request: EmailRequest = EmailRequest {
+            bounce_address: self.bounce_address,
+            sender: self.sender.unwrap_or_else(|| EmailAddress::new("")),
+            recipients: self.recipients,
+            reply_to: self.reply_to,
+            subject: self.subject.unwrap_or_default(),
+            htmlbody: self.htmlbody,
+            textbody: self.textbody,
+            carbon_copy: self.carbon_copy,
+            blind_carbon_copy: self.blind_carbon_copy,
+            track_clicks: self.track_clicks,
+            track_opens: self.track_opens,
+            client_reference: self.client_reference,
+            mime_headers: self.mime_headers,
+            attachments: self.attachments,
+            inline_images: self.inline_images,
+        };
+
+        Ok(request)
+    }
+}
+
+/// Builder for a [`BatchEmailRequest`].
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{BatchEmailRequest, Recipient};
+///
+/// let batch_email_request = BatchEmailRequest::builder()
+///     .sender("sender@example.com")
+///     .to(Recipient::new("recipient1@example.com").with_merge_field("name", "Recipient One"))
+///     .to(Recipient::new("recipient2@example.com").with_merge_field("name", "Recipient Two"))
+///     .subject("Hello {{name}}")
+///     .html("<div>Hello {{name}}</div>")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(batch_email_request.recipients.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BatchEmailRequestBuilder {
+    sender: Option<EmailAddress>,
+    recipients: Vec<Recipient>,
+    subject: Option<String>,
+    htmlbody: Option<String>,
+    textbody: Option<String>,
+    carbon_copy: Option<Vec<Recipient>>,
+    blind_carbon_copy: Option<Vec<Recipient>>,
+    track_clicks: Option<bool>,
+    track_opens: Option<bool>,
+    client_reference: Option<String>,
+    mime_headers: Option<MimeHeaders>,
+    attachments: Option<Vec<Attachment>>,
+    inline_images: Option<Vec<InlineImage>>,
+}
+
+impl BatchEmailRequestBuilder {
+    pub fn sender(mut self, sender: impl Into<EmailAddress>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    /// Adds a recipient, who receives their own copy of the email, personalized with their merge info.
+    pub fn to(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.recipients.push(recipient.into());
+        self
+    }
+
+    pub fn cc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn bcc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.blind_carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    pub fn html(mut self, htmlbody: impl Into<String>) -> Self {
+        self.htmlbody = Some(htmlbody.into());
+        self
+    }
+
+    pub fn text(mut self, textbody: impl Into<String>) -> Self {
+        self.textbody = Some(textbody.into());
+        self
+    }
+
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    pub fn inline_image(mut self, inline_image: InlineImage) -> Self {
+        self.inline_images.get_or_insert_with(Vec::new).push(inline_image);
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.mime_headers
+            .get_or_insert_with(MimeHeaders::default)
+            .headers
+            .insert(name.into(), value.into());
+        self
+    }
+
+    pub fn track_clicks(mut self, track_clicks: bool) -> Self {
+        self.track_clicks = Some(track_clicks);
+        self
+    }
+
+    pub fn track_opens(mut self, track_opens: bool) -> Self {
+        self.track_opens = Some(track_opens);
+        self
+    }
+
+    pub fn client_reference(mut self, client_reference: impl Into<String>) -> Self {
+        self.client_reference = Some(client_reference.into());
+        self
+    }
+
+    /// Builds the request.
+    ///
+    /// Fails with `ZeptoMailError::Validation` if the sender or recipients are missing.
+    ///
+    /// Other problems, e.g. an invalid address or a blocked attachment, are checked by the client
+    /// against its [`ValidationRules`](crate::validation::ValidationRules) when the request is sent,
+    /// unless validation is turned off with
+    /// [`validate_requests`](crate::ZeptoMailClientBuilder::validate_requests). Call
+    /// [`Validate::validate`](crate::validation::Validate::validate) to check them earlier.
+    pub fn build(self) -> Result<BatchEmailRequest, ZeptoMailError> {
+        check_required_fields(None, self.sender.as_ref(), &self.recipients).map_err(ZeptoMailError::Validation)?;
+
+        let request: BatchEmailRequest = BatchEmailRequest {
+            sender: self.sender.unwrap_or_else(|| EmailAddress::new("")),
+            recipients: self.recipients,
+            subject: self.subject.unwrap_or_default(),
+            htmlbody: self.htmlbody,
+            textbody: self.textbody,
+            carbon_copy: self.carbon_copy,
+            blind_carbon_copy: self.blind_carbon_copy,
+            track_clicks: self.track_clicks,
+            track_opens: self.track_opens,
+            client_reference: self.client_reference,
+            mime_headers: self.mime_headers,
+            attachments: self.attachments,
+            inline_images: self.inline_images,
+        };
+
+        Ok(request)
+    }
+}
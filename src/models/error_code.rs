@@ -0,0 +1,179 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// An error code returned by the ZeptoMail API, in `ApiError.code` or `ApiErrorDetail.code`.
+///
+/// ZeptoMail reports a general error code (`TM_xxxx`) on the error itself and a more specific
+/// sub-code in each of its details. Codes this crate does not know about are kept as
+/// [`ZeptoErrorCode::Unknown`], so new codes never break deserialization.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{ApiError, ZeptoErrorCode};
+///
+/// let api_error: ApiError = serde_json::from_str(r#"{
+///     "code": "TM_4001",
+///     "message": "Access Denied",
+///     "details": [{ "code": "SERR_157", "message": "Invalid API Token found", "target": null }],
+///     "request_id": "2d6f.1e0b"
+/// }"#).unwrap();
+///
+/// assert_eq!(api_error.code, ZeptoErrorCode::AccessDenied);
+/// assert!(api_error.code.is_auth_failure());
+/// assert_eq!(ZeptoErrorCode::from("TM_9999"), ZeptoErrorCode::Unknown("TM_9999".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ZeptoErrorCode {
+    //General error codes.
+    /// `TM_3201`: a mandatory field is missing.
+    MandatoryFieldMissing,
+    /// `TM_3301`: a field has an invalid value or the request body is malformed.
+    InvalidValue,
+    /// `TM_3501`: a referenced resource, such as a template or Mail Agent, was not found.
+    ResourceNotFound,
+    /// `TM_3601`: the sender address or domain is not verified.
+    SenderNotVerified,
+    /// `TM_4001`: access was denied, e.g. because of an invalid API token.
+    AccessDenied,
+    /// `TM_5001`: the account has no usable credits.
+    CreditsUnavailable,
+    /// `TM_8001`: too many requests were sent in a short time.
+    TooManyRequests,
+
+    //Sub-codes.
+    /// `GE_102`: a required parameter is missing.
+    MissingParameter,
+    /// `SM_101`: the request body is not valid JSON.
+    InvalidJson,
+    /// `SM_111`: a recipient address is invalid.
+    InvalidRecipient,
+    /// `SM_113`: the sender address is invalid.
+    InvalidSender,
+    /// `SM_120`: an attachment is invalid or of a blocked type.
+    InvalidAttachment,
+    /// `SM_128`: sending has been blocked for the account.
+    AccountBlocked,
+    /// `SM_133`: the trial account's sending limit was exceeded.
+    TrialLimitExceeded,
+    /// `SERR_156`: the sender domain is not verified for the Mail Agent.
+    UnverifiedDomain,
+    /// `SERR_157`: the API token is invalid.
+    InvalidApiToken,
+    /// `LE_101`: the account's credits have expired.
+    CreditsExpired,
+    /// `LE_102`: the account's credits are exhausted.
+    CreditsExhausted,
+    /// `UE_280`: the template key does not exist.
+    TemplateNotFound,
+
+    /// Any code not listed above.
+    Unknown(String),
+}
+
+impl ZeptoErrorCode {
+    const KNOWN: [(&'static str, ZeptoErrorCode); 19] = [
+        ("TM_3201", ZeptoErrorCode::MandatoryFieldMissing),
+        ("TM_3301", ZeptoErrorCode::InvalidValue),
+        ("TM_3501", ZeptoErrorCode::ResourceNotFound),
+        ("TM_3601", ZeptoErrorCode::SenderNotVerified),
+        ("TM_4001", ZeptoErrorCode::AccessDenied),
+        ("TM_5001", ZeptoErrorCode::CreditsUnavailable),
+        ("TM_8001", ZeptoErrorCode::TooManyRequests),
+        ("GE_102", ZeptoErrorCode::MissingParameter),
+        ("SM_101", ZeptoErrorCode::InvalidJson),
+        ("SM_111", ZeptoErrorCode::InvalidRecipient),
+        ("SM_113", ZeptoErrorCode::InvalidSender),
+        ("SM_120", ZeptoErrorCode::InvalidAttachment),
+        ("SM_128", ZeptoErrorCode::AccountBlocked),
+        ("SM_133", ZeptoErrorCode::TrialLimitExceeded),
+        ("SERR_156", ZeptoErrorCode::UnverifiedDomain),
+        ("SERR_157", ZeptoErrorCode::InvalidApiToken),
+        ("LE_101", ZeptoErrorCode::CreditsExpired),
+        ("LE_102", ZeptoErrorCode::CreditsExhausted),
+        ("UE_280", ZeptoErrorCode::TemplateNotFound),
+    ];
+
+    /// The code as sent by ZeptoMail, e.g. `"TM_4001"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ZeptoErrorCode::Unknown(code) => code,
+            known => Self::KNOWN
+                .iter()
+                .find(|(_, code)| code == known)
+                .map(|(name, _)| *name)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether the API key was rejected.
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(self, ZeptoErrorCode::AccessDenied | ZeptoErrorCode::InvalidApiToken)
+    }
+
+    /// Whether a recipient address was rejected.
+    pub fn is_invalid_recipient(&self) -> bool {
+        matches!(self, ZeptoErrorCode::InvalidRecipient)
+    }
+
+    /// Whether ZeptoMail throttled the request.
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, ZeptoErrorCode::TooManyRequests)
+    }
+
+    /// Whether a request that failed with this code may succeed when retried unchanged.
+    ///
+    /// Only throttling is retryable: every other known code means the request or the account has
+    /// to change first. Unknown codes are not retryable either; callers should fall back to the
+    /// HTTP status, as [`ZeptoMailError::is_retryable`](crate::ZeptoMailError::is_retryable) does.
+    pub fn is_retryable(&self) -> bool {
+        self.is_rate_limited()
+    }
+
+    /// Whether the code is one this crate knows about.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, ZeptoErrorCode::Unknown(_))
+    }
+}
+
+impl From<&str> for ZeptoErrorCode {
+    fn from(code: &str) -> Self {
+        Self::KNOWN
+            .iter()
+            .find(|(name, _)| *name == code)
+            .map(|(_, known)| known.clone())
+            .unwrap_or_else(|| ZeptoErrorCode::Unknown(code.to_string()))
+    }
+}
+
+impl From<String> for ZeptoErrorCode {
+    fn from(code: String) -> Self {
+        ZeptoErrorCode::from(code.as_str())
+    }
+}
+
+impl From<ZeptoErrorCode> for String {
+    fn from(code: ZeptoErrorCode) -> Self {
+        code.as_str().to_string()
+    }
+}
+
+impl PartialEq<str> for ZeptoErrorCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ZeptoErrorCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for ZeptoErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,15 +1,269 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
 use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::io::ReaderStream;
+
+use crate::models::content;
+use crate::transport::BodyStream;
+use crate::ZeptoMailError;
+
+/// How long ZeptoMail keeps a file in the cache after it was uploaded: six months.
+pub const FILE_CACHE_TTL: Duration = Duration::from_secs(180 * 24 * 60 * 60);
+
+//How many bytes of a file are read to infer its MIME type.
+const SNIFF_LEN: u64 = 16;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A file to upload to ZeptoMail's file cache.
+///
+/// The content is sent as the raw request body. Files and streams are not loaded into memory, but
+/// read while the request is sent.
+#[non_exhaustive]
 pub struct FileUploadRequest {
+    //The file name, sent as the `name` query parameter.
     pub name: String,
+    //The MIME type of the file, sent as the Content-Type header.
     pub content_type: String,
-    pub data: Vec<u8>, // Binary data of the file
+    pub(crate) source: UploadSource,
+    pub(crate) progress: Option<ProgressCallback>,
+}
+
+/// How far an upload has come, passed to the callback set with
+/// [`FileUploadRequest::on_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+impl UploadProgress {
+    /// Whether all bytes were sent.
+    pub fn is_complete(&self) -> bool {
+        self.bytes_sent >= self.total_bytes
+    }
+}
+
+pub(crate) type ProgressCallback = Arc<dyn Fn(UploadProgress) + Send + Sync>;
+
+pub(crate) enum UploadSource {
+    Bytes(Bytes),
+    //Reopened for every attempt, so uploads from a path can be retried.
+    Path { path: PathBuf, length: u64 },
+    //Taken by the first attempt.
+    Stream { stream: Mutex<Option<BodyStream>>, length: u64 },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl FileUploadRequest {
+    /// A file with the given content.
+    pub fn new(name: impl Into<String>, content_type: impl Into<String>, data: impl Into<Bytes>) -> Self {
+        FileUploadRequest {
+            name: name.into(),
+            content_type: content_type.into(),
+            source: UploadSource::Bytes(data.into()),
+            progress: None,
+        }
+    }
+
+    /// A file with the given content, with the MIME type inferred from the content and the
+    /// extension of `name`.
+    pub fn from_bytes(name: impl Into<String>, data: impl Into<Bytes>) -> Self {
+        let name: String = name.into();
+        let data: Bytes = data.into();
+        FileUploadRequest {
+            content_type: content::detect_mime_type(&name, &data),
+            name,
+            source: UploadSource::Bytes(data),
+            progress: None,
+        }
+    }
+
+    /// The file at `path`, named after the file, with the MIME type inferred from its first bytes
+    /// and its extension. The file is streamed when the request is sent.
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::IoError` - If the file cannot be opened.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, FileUploadRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu).unwrap();
+    ///
+    ///     let file_upload_request = FileUploadRequest::from_path("terms.pdf").await.unwrap();
+    ///     let response = client.upload_file_to_cache(file_upload_request).await.unwrap();
+    ///     println!("{} expires at {:?}", response.file_cache_key, response.expires_at);
+    /// }
+    /// ```
+    pub async fn from_path(path: impl AsRef<Path>) -> Result<Self, ZeptoMailError> {
+        let path: &Path = path.as_ref();
+        let name: String = content::file_name(path)?;
+
+        let file: tokio::fs::File = tokio::fs::File::open(path).await.map_err(ZeptoMailError::IoError)?;
+        let length: u64 = file.metadata().await.map_err(ZeptoMailError::IoError)?.len();
+        let mut head: Vec<u8> = Vec::new();
+        file.take(SNIFF_LEN).read_to_end(&mut head).await.map_err(ZeptoMailError::IoError)?;
+
+        Ok(FileUploadRequest {
+            content_type: content::detect_mime_type(&name, &head),
+            name,
+            source: UploadSource::Path { path: path.to_path_buf(), length },
+            progress: None,
+        })
+    }
+
+    /// A file whose `length` bytes are produced by `stream`. A stream of plain `Bytes` can be
+    /// passed as `stream.map(Ok::<_, std::io::Error>)`.
+    ///
+    /// The upload fails if the stream yields more or fewer than `length` bytes. A stream can only be
+    /// read once, so the upload is not retried.
+    pub fn from_stream<S, E>(
+        name: impl Into<String>,
+        content_type: impl Into<String>,
+        length: u64,
+        stream: S
+    ) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+        E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+    {
+        let stream: BodyStream = Box::pin(stream.map_err(|err| {
+            let err: Box<dyn StdError + Send + Sync> = err.into();
+            match err.downcast::<std::io::Error>() {
+                Ok(err) => *err,
+                Err(err) => std::io::Error::other(err),
+            }
+        }));
+        FileUploadRequest {
+            name: name.into(),
+            content_type: content_type.into(),
+            source: UploadSource::Stream { stream: Mutex::new(Some(stream)), length },
+            progress: None,
+        }
+    }
+
+    /// A file whose `length` bytes are read from `reader` while the request is sent.
+    ///
+    /// The upload fails if the reader yields more or fewer than `length` bytes. A reader can only be
+    /// read once, so the upload is not retried.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, FileUploadRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu).unwrap();
+    ///
+    ///     let report = tokio::fs::File::open("report.pdf").await.unwrap();
+    ///     let length = report.metadata().await.unwrap().len();
+    ///     let (progress_tx, mut progress_rx) = tokio::sync::watch::channel(None);
+    ///
+    ///     let file_upload_request = FileUploadRequest::from_reader("report.pdf", "application/pdf", length, report)
+    ///         .on_progress(move |progress| {
+    ///             let _ = progress_tx.send(Some(progress));
+    ///         });
+    ///
+    ///     tokio::spawn(async move {
+    ///         while progress_rx.changed().await.is_ok() {
+    ///             if let Some(progress) = *progress_rx.borrow() {
+    ///                 println!("{} of {} bytes sent", progress.bytes_sent, progress.total_bytes);
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     client.upload_file_to_cache(file_upload_request).await.unwrap();
+    /// }
+    /// ```
+    pub fn from_reader<R>(
+        name: impl Into<String>,
+        content_type: impl Into<String>,
+        length: u64,
+        reader: R
+    ) -> Self
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        FileUploadRequest::from_stream(name, content_type, length, ReaderStream::new(reader))
+    }
+
+    /// Calls `callback` with the number of bytes sent so far, every time a chunk of the file was
+    /// handed to the transport. To receive progress on a channel, send to it from the callback.
+    ///
+    /// When an upload is retried, progress starts over from zero.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Overrides the inferred MIME type.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// The size of the file in bytes.
+    pub fn len(&self) -> u64 {
+        match &self.source {
+            UploadSource::Bytes(data) => data.len() as u64,
+            UploadSource::Path { length, .. } | UploadSource::Stream { length, .. } => *length,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for FileUploadRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("FileUploadRequest");
+        debug.field("name", &self.name).field("content_type", &self.content_type);
+        match &self.source {
+            UploadSource::Bytes(data) => debug.field("len", &data.len()),
+            UploadSource::Path { path, length } => debug.field("path", path).field("len", length),
+            UploadSource::Stream { length, .. } => debug.field("len", length),
+        };
+        debug.field("on_progress", &self.progress.is_some()).finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileUploadResponse {
+    //The key to refer to the file with in `Attachment::from_file_cache`.
     pub file_cache_key: String,
+    #[serde(default)]
+    pub data: Vec<serde_json::Value>,
     pub message: String,
-    pub code: String,
+    //The type of object that was created, `files`.
+    #[serde(default)]
+    pub object: Option<String>,
+    #[serde(default)]
+    pub code: Option<String>,
+    //When ZeptoMail removes the file from the cache, `FILE_CACHE_TTL` after the upload.
+    #[serde(skip)]
+    pub expires_at: Option<SystemTime>,
+    //How many attempts it took to get this response, including the first one.
+    #[serde(skip)]
+    pub attempts: u32,
+}
+
+impl FileUploadResponse {
+    /// Whether the file cache key has expired and the file has to be uploaded again.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= SystemTime::now())
+    }
 }
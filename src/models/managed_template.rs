@@ -0,0 +1,199 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::render;
+use crate::request::ResponseMetadata;
+use crate::MergeInfo;
+
+/// A template stored in a Mail Agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Template {
+    //The key emails are sent with, e.g. in `TemplateEmailRequest::builder`.
+    pub template_key: String,
+    pub template_name: String,
+    //An alternative, human-chosen identifier for the template.
+    pub template_alias: Option<String>,
+    #[serde(default)]
+    pub subject: String,
+    pub htmlbody: Option<String>,
+    //Example values for the template's merge fields, used by ZeptoMail to preview the template.
+    pub sample_merge_info: Option<MergeInfo>,
+    pub created_time: Option<String>,
+    pub modified_time: Option<String>,
+}
+
+impl Template {
+    /// The names of the `{{placeholders}}` in the subject and HTML body, in order of first appearance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zeptomail_rs::Template;
+    ///
+    /// let template: Template = serde_json::from_str(r#"{
+    ///     "template_key": "2d6f.1e0b",
+    ///     "template_name": "Order confirmation",
+    ///     "subject": "Order {{order.id}}",
+    ///     "htmlbody": "<p>Hi {{name}}, order {{order.id}} is confirmed.</p>"
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(template.merge_fields(), vec!["order.id", "name"]);
+    /// ```
+    pub fn merge_fields(&self) -> Vec<String> {
+        let empty: MergeInfo = MergeInfo::new();
+        let mut merge_fields: Vec<String> = render::render(&self.subject, &empty).unresolved;
+        for name in render::render(self.htmlbody.as_deref().unwrap_or_default(), &empty).unresolved {
+            if !merge_fields.contains(&name) {
+                merge_fields.push(name);
+            }
+        }
+        merge_fields
+    }
+}
+
+/// The content of a template to create, or to replace an existing template's content with.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use zeptomail_rs::{MergeData, TemplateDefinition};
+///
+/// let definition = TemplateDefinition::new(
+///     "Order confirmation",
+///     "Order {{order_id}}",
+///     "<p>Hi {{name}}, order {{order_id}} is confirmed.</p>",
+/// )
+/// .alias("order-confirmation")
+/// .sample_merge_info(json!({ "name": "Jane", "order_id": 1042 }).to_merge_info().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TemplateDefinition {
+    pub template_name: String,
+    pub subject: String,
+    pub htmlbody: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_alias: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_merge_info: Option<MergeInfo>,
+}
+
+impl TemplateDefinition {
+    pub fn new(
+        template_name: impl Into<String>,
+        subject: impl Into<String>,
+        htmlbody: impl Into<String>
+    ) -> Self {
+        TemplateDefinition {
+            template_name: template_name.into(),
+            subject: subject.into(),
+            htmlbody: htmlbody.into(),
+            template_alias: None,
+            sample_merge_info: None,
+        }
+    }
+
+    pub fn alias(mut self, template_alias: impl Into<String>) -> Self {
+        self.template_alias = Some(template_alias.into());
+        self
+    }
+
+    pub fn sample_merge_info(mut self, sample_merge_info: MergeInfo) -> Self {
+        self.sample_merge_info = Some(sample_merge_info);
+        self
+    }
+}
+
+impl From<&Template> for TemplateDefinition {
+    fn from(template: &Template) -> Self {
+        TemplateDefinition {
+            template_name: template.template_name.clone(),
+            subject: template.subject.clone(),
+            htmlbody: template.htmlbody.clone().unwrap_or_default(),
+            template_alias: template.template_alias.clone(),
+            sample_merge_info: template.sample_merge_info.clone(),
+        }
+    }
+}
+
+/// Which page of a Mail Agent's templates to list.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::TemplateListOptions;
+///
+/// let options = TemplateListOptions::new().offset(50).limit(25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateListOptions {
+    pub(crate) offset: u32,
+    pub(crate) limit: u32,
+}
+
+impl TemplateListOptions {
+    /// The first 10 templates.
+    pub fn new() -> Self {
+        TemplateListOptions { offset: 0, limit: 10 }
+    }
+
+    /// Sets the number of templates to skip.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the number of templates per page, at least 1.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+}
+
+impl Default for TemplateListOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One page of a Mail Agent's templates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplatePage {
+    pub templates: Vec<Template>,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+impl TemplatePage {
+    /// The options for the page after this one, or `None` if this is the last page.
+    pub fn next_page(&self) -> Option<TemplateListOptions> {
+        if (self.templates.len() as u32) < self.limit {
+            return None;
+        }
+        Some(TemplateListOptions::new().offset(self.offset + self.limit).limit(self.limit))
+    }
+}
+
+/// The body of a template API response. Single templates are returned as an object or as an array
+/// with one element, lists as an array.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TemplateResponse {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub(crate) data: Vec<Template>,
+}
+
+impl ResponseMetadata for TemplateResponse {}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Template>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(Box<Template>),
+        Many(Vec<Template>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(template) => vec![*template],
+        OneOrMany::Many(templates) => templates,
+    })
+}
@@ -0,0 +1,68 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::ZeptoMailError;
+
+/// The values for a template's `{{placeholders}}`, as a JSON object. Values can be strings,
+/// numbers, booleans, nested objects and arrays.
+pub type MergeInfo = serde_json::Map<String, Value>;
+
+/// Data for a template's merge fields.
+///
+/// Every type that serializes to a JSON object is merge data, so a `#[derive(Serialize)]` struct
+/// can declare the variables a template uses. Nested structs and vectors become the nested objects
+/// and arrays ZeptoMail templates can loop over. The local [renderer](crate::render) does not
+/// support loops.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use zeptomail_rs::{MergeData, Recipient};
+///
+/// #[derive(Serialize)]
+/// struct OrderItem {
+///     name: String,
+///     quantity: u32,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct OrderConfirmation {
+///     name: String,
+///     order_id: u64,
+///     items: Vec<OrderItem>,
+/// }
+///
+/// let order = OrderConfirmation {
+///     name: "Jane".to_string(),
+///     order_id: 1042,
+///     items: vec![OrderItem { name: "Keyboard".to_string(), quantity: 1 }],
+/// };
+///
+/// let recipient = Recipient::new("jane@example.com").with_merge_data(&order).unwrap();
+/// let merge_info = recipient.merge_info.unwrap();
+///
+/// assert_eq!(merge_info["order_id"], 1042);
+/// assert_eq!(merge_info["items"][0]["name"], "Keyboard");
+/// assert!(42.to_merge_info().is_err());
+/// ```
+pub trait MergeData {
+    /// Serializes the data into [`MergeInfo`].
+    ///
+    /// # Errors
+    ///
+    /// * `ZeptoMailError::SerializationError` - If serialization fails or the data is not a JSON object.
+    fn to_merge_info(&self) -> Result<MergeInfo, ZeptoMailError>;
+}
+
+impl<T: Serialize + ?Sized> MergeData for T {
+    fn to_merge_info(&self) -> Result<MergeInfo, ZeptoMailError> {
+        match serde_json::to_value(self)? {
+            Value::Object(merge_info) => Ok(merge_info),
+            other => Err(ZeptoMailError::SerializationError(serde::ser::Error::custom(format!(
+                "merge data must serialize to a JSON object, not {}",
+                other
+            )))),
+        }
+    }
+}
@@ -1,6 +1,12 @@
 pub mod api_failure;
 pub mod api_success;
 pub mod common;
+pub(crate) mod content;
 pub mod email;
+pub mod error_code;
 pub mod file_cache;
+pub mod managed_template;
+pub mod merge;
+pub mod suppression;
 pub mod template;
+pub mod webhook;
@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+
+use crate::request::ResponseMetadata;
+
+/// Whether a suppression applies to a single address or to a whole domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuppressionType {
+    #[serde(alias = "email_address")]
+    Email,
+    Domain,
+}
+
+impl SuppressionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuppressionType::Email => "email",
+            SuppressionType::Domain => "domain",
+        }
+    }
+}
+
+/// Why an address or domain is suppressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SuppressionReason {
+    #[serde(alias = "hardbounce")]
+    HardBounce,
+    #[serde(alias = "spam", alias = "feedback_loop")]
+    SpamComplaint,
+    #[serde(alias = "unsubscribe")]
+    Unsubscribed,
+    //Added through the API or the dashboard.
+    Manual,
+    #[serde(other)]
+    Other,
+}
+
+impl SuppressionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuppressionReason::HardBounce => "hard_bounce",
+            SuppressionReason::SpamComplaint => "spam_complaint",
+            SuppressionReason::Unsubscribed => "unsubscribed",
+            SuppressionReason::Manual => "manual",
+            SuppressionReason::Other => "other",
+        }
+    }
+}
+
+/// An address or domain ZeptoMail does not send to.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{Suppression, SuppressionType};
+///
+/// let address = Suppression::email("jane@example.com");
+/// let domain = Suppression::domain("example.org");
+///
+/// assert_eq!(domain.kind, SuppressionType::Domain);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    #[serde(rename = "type")]
+    pub kind: SuppressionType,
+    //The email address or domain.
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<SuppressionReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+}
+
+impl Suppression {
+    pub fn email(address: impl Into<String>) -> Self {
+        Suppression {
+            kind: SuppressionType::Email,
+            value: address.into(),
+            reason: None,
+            created_time: None,
+        }
+    }
+
+    pub fn domain(domain: impl Into<String>) -> Self {
+        Suppression {
+            kind: SuppressionType::Domain,
+            value: domain.into(),
+            reason: None,
+            created_time: None,
+        }
+    }
+
+    /// Whether the suppression covers `address`, either as the address itself or as its domain.
+    /// Addresses are compared ignoring case, as the client's suppression check does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zeptomail_rs::Suppression;
+    ///
+    /// assert!(Suppression::email("Änne@Example.org").matches("änne@example.org"));
+    /// assert!(Suppression::domain("EXAMPLE.org").matches("jane@example.ORG"));
+    /// assert!(!Suppression::domain("example.org").matches("jane@mail.example.org"));
+    /// ```
+    pub fn matches(&self, address: &str) -> bool {
+        let address: String = normalize_address(address);
+        let value: String = normalize_address(&self.value);
+        match self.kind {
+            SuppressionType::Email => address == value,
+            SuppressionType::Domain => address_domain(&address) == Some(value.as_str()),
+        }
+    }
+}
+
+/// The form addresses and domains are compared in when checking suppressions.
+pub(crate) fn normalize_address(address: &str) -> String {
+    address.to_lowercase()
+}
+
+/// The domain of `address`, if it has one.
+pub(crate) fn address_domain(address: &str) -> Option<&str> {
+    address.rsplit_once('@').map(|(_, domain)| domain)
+}
+
+/// Which page of the suppression list to list, and which entries to include.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{SuppressionListOptions, SuppressionReason, SuppressionType};
+///
+/// let options = SuppressionListOptions::new()
+///     .limit(100)
+///     .kind(SuppressionType::Email)
+///     .reason(SuppressionReason::HardBounce)
+///     .search("example.com");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressionListOptions {
+    pub(crate) offset: u32,
+    pub(crate) limit: u32,
+    pub(crate) kind: Option<SuppressionType>,
+    pub(crate) reason: Option<SuppressionReason>,
+    pub(crate) search: Option<String>,
+}
+
+impl SuppressionListOptions {
+    /// The first 10 entries of any type and reason.
+    pub fn new() -> Self {
+        SuppressionListOptions {
+            offset: 0,
+            limit: 10,
+            kind: None,
+            reason: None,
+            search: None,
+        }
+    }
+
+    /// Sets the number of entries to skip.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the number of entries per page, at least 1.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+
+    /// Only lists addresses or only domains.
+    pub fn kind(mut self, kind: SuppressionType) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only lists entries suppressed for `reason`.
+    pub fn reason(mut self, reason: SuppressionReason) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Only lists entries that contain `search`.
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+}
+
+impl Default for SuppressionListOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One page of the suppression list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuppressionPage {
+    pub suppressions: Vec<Suppression>,
+    //The options the page was listed with.
+    pub options: SuppressionListOptions,
+}
+
+impl SuppressionPage {
+    /// The options for the page after this one, with the same filters, or `None` if this is the last page.
+    pub fn next_page(&self) -> Option<SuppressionListOptions> {
+        if (self.suppressions.len() as u32) < self.options.limit {
+            return None;
+        }
+        Some(self.options.clone().offset(self.options.offset + self.options.limit))
+    }
+}
+
+/// The body of a suppression API response.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SuppressionResponse {
+    #[serde(default)]
+    pub(crate) data: Vec<Suppression>,
+}
+
+impl ResponseMetadata for SuppressionResponse {}
+
+/// The body of a request that adds or removes suppressions.
+#[derive(Debug, Serialize)]
+pub(crate) struct SuppressionRequest<'a> {
+    pub(crate) data: &'a [Suppression],
+}
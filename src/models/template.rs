@@ -1,35 +1,362 @@
-use std::collections::HashMap;
-
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{Attachment, EmailAddress, MimeHeaders, Recipient};
+use crate::validation::check_required_fields;
+use crate::{Attachment, EmailAddress, InlineImage, MergeData, MergeInfo, MimeHeaders, Recipient, ZeptoMailError};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct TemplateEmailRequest {
     pub template_key: String,
     pub bounce_address: Option<String>,
     pub sender: EmailAddress,
     pub recipients: Vec<Recipient>,
     pub reply_to: Option<Vec<EmailAddress>>,
+    pub carbon_copy: Option<Vec<Recipient>>,
+    pub blind_carbon_copy: Option<Vec<Recipient>>,
     pub track_clicks: Option<bool>,
     pub track_opens: Option<bool>,
     pub client_reference: Option<String>,
     pub mime_headers: Option<MimeHeaders>,
     pub attachments: Option<Vec<Attachment>>,
-    pub merge_info: Option<HashMap<String, String>>, 
+    //Images the template references with `cid:`.
+    pub inline_images: Option<Vec<InlineImage>>,
+    pub merge_info: Option<MergeInfo>,
+}
 
+impl TemplateEmailRequest {
+    /// Returns a builder for an email rendered from the template with the given key.
+    pub fn builder(template_key: impl Into<String>) -> TemplateEmailRequestBuilder {
+        TemplateEmailRequestBuilder {
+            template_key: template_key.into(),
+            ..Default::default()
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BatchTemplateEmailRequest {
     pub template_key: String,
     pub bounce_address: Option<String>,
     pub sender: EmailAddress,
     pub recipients: Vec<Recipient>,
     pub reply_to: Option<Vec<EmailAddress>>,
+    pub carbon_copy: Option<Vec<Recipient>>,
+    pub blind_carbon_copy: Option<Vec<Recipient>>,
     pub track_clicks: Option<bool>,
     pub track_opens: Option<bool>,
     pub client_reference: Option<String>,
     pub mime_headers: Option<MimeHeaders>,
     pub attachments: Option<Vec<Attachment>>,
+    //Images the template references with `cid:`.
+    pub inline_images: Option<Vec<InlineImage>>,
+}
+
+impl BatchTemplateEmailRequest {
+    /// Returns a builder for a batch of emails rendered from the template with the given key.
+    pub fn builder(template_key: impl Into<String>) -> BatchTemplateEmailRequestBuilder {
+        BatchTemplateEmailRequestBuilder {
+            template_key: template_key.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Builder for a [`TemplateEmailRequest`].
+///
+/// There are no `subject`, `html` or `text` methods: the subject and body come from the template.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{TemplateEmailRequest, EmailAddress};
+///
+/// let template_email_request = TemplateEmailRequest::builder("your_template_key")
+///     .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+///     .to("recipient@example.com")
+///     .cc("manager@example.com")
+///     .merge_field("name", "Recipient")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(template_email_request.template_key, "your_template_key");
+/// assert_eq!(template_email_request.carbon_copy.unwrap().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateEmailRequestBuilder {
+    template_key: String,
+    bounce_address: Option<String>,
+    sender: Option<EmailAddress>,
+    recipients: Vec<Recipient>,
+    reply_to: Option<Vec<EmailAddress>>,
+    carbon_copy: Option<Vec<Recipient>>,
+    blind_carbon_copy: Option<Vec<Recipient>>,
+    track_clicks: Option<bool>,
+    track_opens: Option<bool>,
+    client_reference: Option<String>,
+    mime_headers: Option<MimeHeaders>,
+    attachments: Option<Vec<Attachment>>,
+    inline_images: Option<Vec<InlineImage>>,
+    merge_info: Option<MergeInfo>,
+    //A `merge_data` serialization failure, reported by `build`.
+    merge_error: Option<String>,
+}
+
+impl TemplateEmailRequestBuilder {
+    pub fn bounce_address(mut self, address: impl Into<String>) -> Self {
+        self.bounce_address = Some(address.into());
+        self
+    }
+
+    pub fn sender(mut self, sender: impl Into<EmailAddress>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    pub fn to(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.recipients.push(recipient.into());
+        self
+    }
+
+    pub fn cc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn bcc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.blind_carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn reply_to(mut self, address: impl Into<EmailAddress>) -> Self {
+        self.reply_to.get_or_insert_with(Vec::new).push(address.into());
+        self
+    }
+
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    /// Adds an image the template references with `cid:`.
+    pub fn inline_image(mut self, inline_image: InlineImage) -> Self {
+        self.inline_images.get_or_insert_with(Vec::new).push(inline_image);
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.mime_headers
+            .get_or_insert_with(MimeHeaders::default)
+            .headers
+            .insert(name.into(), value.into());
+        self
+    }
+
+    pub fn track_clicks(mut self, track_clicks: bool) -> Self {
+        self.track_clicks = Some(track_clicks);
+        self
+    }
+
+    pub fn track_opens(mut self, track_opens: bool) -> Self {
+        self.track_opens = Some(track_opens);
+        self
+    }
+
+    pub fn client_reference(mut self, client_reference: impl Into<String>) -> Self {
+        self.client_reference = Some(client_reference.into());
+        self
+    }
+
+    /// Adds a value for the template's `{{key}}` merge field.
+    pub fn merge_field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.merge_info
+            .get_or_insert_with(MergeInfo::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds the fields of `data`, e.g. a `#[derive(Serialize)]` struct, as merge fields. See [`MergeData`].
+    ///
+    /// If `data` does not serialize to a JSON object, [`build`](Self::build) fails with
+    /// `ZeptoMailError::SerializationError`.
+    pub fn merge_data<T: MergeData + ?Sized>(mut self, data: &T) -> Self {
+        match data.to_merge_info() {
+            Ok(merge_info) => self.merge_info.get_or_insert_with(MergeInfo::new).extend(merge_info),
+            Err(error) => self.merge_error = Some(error.to_string()),
+        }
+        self
+    }
+
+    /// Builds the request.
+    ///
+    /// Fails with `ZeptoMailError::Validation` if the template key, sender or recipients are missing,
+    /// and with `ZeptoMailError::SerializationError` if data passed to [`merge_data`](Self::merge_data)
+    /// could not be serialized.
+    ///
+    /// Other problems, e.g. an invalid address or a blocked attachment, are checked by the client
+    /// against its [`ValidationRules`](crate::validation::ValidationRules) when the request is sent,
+    /// unless validation is turned off with
+    /// [`validate_requests`](crate::ZeptoMailClientBuilder::validate_requests). Call
+    /// [`Validate::validate`](crate::validation::Validate::validate) to check them earlier.
+    pub fn build(self) -> Result<TemplateEmailRequest, ZeptoMailError> {
+        if let Some(message) = self.merge_error {
+            return Err(ZeptoMailError::SerializationError(serde::ser::Error::custom(message)));
+        }
+        check_required_fields(Some(&self.template_key), self.sender.as_ref(), &self.recipients)
+            .map_err(ZeptoMailError::Validation)?;
+
+        let request: TemplateEmailRequest = TemplateEmailRequest {
+            template_key: self.template_key,
+            bounce_address: self.bounce_address,
+            sender: self.sender.unwrap_or_else(|| EmailAddress::new("")),
+            recipients: self.recipients,
+            reply_to: self.reply_to,
+            carbon_copy: self.carbon_copy,
+            blind_carbon_copy: self.blind_carbon_copy,
+            track_clicks: self.track_clicks,
+            track_opens: self.track_opens,
+            client_reference: self.client_reference,
+            mime_headers: self.mime_headers,
+            attachments: self.attachments,
+            inline_images: self.inline_images,
+            merge_info: self.merge_info,
+        };
+
+        Ok(request)
+    }
+}
+
+/// Builder for a [`BatchTemplateEmailRequest`].
+///
+/// There are no `subject`, `html` or `text` methods: the subject and body come from the template.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{BatchTemplateEmailRequest, Recipient};
+///
+/// let batch_template_email_request = BatchTemplateEmailRequest::builder("your_template_key")
+///     .sender("sender@example.com")
+///     .to(Recipient::new("recipient1@example.com").with_merge_field("name", "Recipient One"))
+///     .to(Recipient::new("recipient2@example.com").with_merge_field("name", "Recipient Two"))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(batch_template_email_request.recipients.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BatchTemplateEmailRequestBuilder {
+    template_key: String,
+    bounce_address: Option<String>,
+    sender: Option<EmailAddress>,
+    recipients: Vec<Recipient>,
+    reply_to: Option<Vec<EmailAddress>>,
+    carbon_copy: Option<Vec<Recipient>>,
+    blind_carbon_copy: Option<Vec<Recipient>>,
+    track_clicks: Option<bool>,
+    track_opens: Option<bool>,
+    client_reference: Option<String>,
+    mime_headers: Option<MimeHeaders>,
+    attachments: Option<Vec<Attachment>>,
+    inline_images: Option<Vec<InlineImage>>,
+}
+
+impl BatchTemplateEmailRequestBuilder {
+    pub fn bounce_address(mut self, address: impl Into<String>) -> Self {
+        self.bounce_address = Some(address.into());
+        self
+    }
+
+    pub fn sender(mut self, sender: impl Into<EmailAddress>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    /// Adds a recipient, who receives their own copy of the email, personalized with their merge info.
+    pub fn to(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.recipients.push(recipient.into());
+        self
+    }
+
+    pub fn cc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn bcc(mut self, recipient: impl Into<Recipient>) -> Self {
+        self.blind_carbon_copy.get_or_insert_with(Vec::new).push(recipient.into());
+        self
+    }
+
+    pub fn reply_to(mut self, address: impl Into<EmailAddress>) -> Self {
+        self.reply_to.get_or_insert_with(Vec::new).push(address.into());
+        self
+    }
+
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    /// Adds an image the template references with `cid:`.
+    pub fn inline_image(mut self, inline_image: InlineImage) -> Self {
+        self.inline_images.get_or_insert_with(Vec::new).push(inline_image);
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.mime_headers
+            .get_or_insert_with(MimeHeaders::default)
+            .headers
+            .insert(name.into(), value.into());
+        self
+    }
+
+    pub fn track_clicks(mut self, track_clicks: bool) -> Self {
+        self.track_clicks = Some(track_clicks);
+        self
+    }
+
+    pub fn track_opens(mut self, track_opens: bool) -> Self {
+        self.track_opens = Some(track_opens);
+        self
+    }
+
+    pub fn client_reference(mut self, client_reference: impl Into<String>) -> Self {
+        self.client_reference = Some(client_reference.into());
+        self
+    }
+
+    /// Builds the request.
+    ///
+    /// Fails with `ZeptoMailError::Validation` if the template key, sender or recipients are missing.
+    ///
+    /// Other problems, e.g. an invalid address or a blocked attachment, are checked by the client
+    /// against its [`ValidationRules`](crate::validation::ValidationRules) when the request is sent,
+    /// unless validation is turned off with
+    /// [`validate_requests`](crate::ZeptoMailClientBuilder::validate_requests). Call
+    /// [`Validate::validate`](crate::validation::Validate::validate) to check them earlier.
+    pub fn build(self) -> Result<BatchTemplateEmailRequest, ZeptoMailError> {
+        check_required_fields(Some(&self.template_key), self.sender.as_ref(), &self.recipients)
+            .map_err(ZeptoMailError::Validation)?;
+
+        let request: BatchTemplateEmailRequest = BatchTemplateEmailRequest {
+            template_key: self.template_key,
+            bounce_address: self.bounce_address,
+            sender: self.sender.unwrap_or_else(|| EmailAddress::new("")),
+            recipients: self.recipients,
+            reply_to: self.reply_to,
+            carbon_copy: self.carbon_copy,
+            blind_carbon_copy: self.blind_carbon_copy,
+            track_clicks: self.track_clicks,
+            track_opens: self.track_opens,
+            client_reference: self.client_reference,
+            mime_headers: self.mime_headers,
+            attachments: self.attachments,
+            inline_images: self.inline_images,
+        };
+
+        Ok(request)
+    }
 }
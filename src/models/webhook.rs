@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{EmailAddress, Recipient};
+
+/// A notification ZeptoMail sent to a webhook about one recipient of an email.
+///
+/// A webhook payload can report several events; [`parse_webhook`](crate::webhooks::parse_webhook)
+/// returns one `WebhookEvent` per event and recipient.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub kind: EventKind,
+    //The `request_id` of the `ApiResponse` the email was sent with.
+    pub request_id: Option<String>,
+    //The `client_reference` the email was sent with.
+    pub client_reference: Option<String>,
+    pub email_info: EmailInfo,
+    //The key of the Mail Agent the email was sent from.
+    pub mail_agent_key: Option<String>,
+    //The ID ZeptoMail assigned to the webhook call; retried calls keep the same ID.
+    pub webhook_request_id: Option<String>,
+}
+
+impl WebhookEvent {
+    /// The address the event is about, e.g. the bounced recipient or the recipient who opened the email.
+    pub fn recipient(&self) -> Option<&str> {
+        let recipient: Option<&str> = match &self.kind {
+            EventKind::HardBounce(bounce) | EventKind::SoftBounce(bounce) => bounce.recipient.as_deref(),
+            EventKind::Open(open) => open.recipient.as_deref(),
+            EventKind::Click(click) => click.recipient.as_deref(),
+            EventKind::SpamComplaint(complaint) => complaint.recipient.as_deref(),
+            EventKind::Delivery(delivery) => delivery.recipient.as_deref(),
+            EventKind::Other { .. } => None,
+        };
+
+        //Emails to a single recipient do not always repeat the address in the event.
+        recipient.or(match self.email_info.to.as_slice() {
+            [to] => Some(to.email_address.address.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// What happened to an email, with the details ZeptoMail reported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "details", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EventKind {
+    /// The recipient's server permanently rejected the email.
+    HardBounce(Bounce),
+    /// The recipient's server temporarily rejected the email, e.g. because the mailbox is full.
+    SoftBounce(Bounce),
+    Open(Open),
+    Click(Click),
+    /// The recipient marked the email as spam.
+    SpamComplaint(SpamComplaint),
+    Delivery(Delivery),
+    /// An event this crate does not model yet, or a known event whose details have an unexpected
+    /// shape, with its raw details.
+    Other { name: String, details: Value },
+}
+
+impl EventKind {
+    /// Builds the event named `name` from its raw details. Details that do not have the shape of
+    /// the event are kept as [`EventKind::Other`], so one odd event does not fail the whole payload.
+    pub(crate) fn from_details(name: &str, details: Value) -> Self {
+        let kind: Result<Self, serde_json::Error> = match name.to_ascii_lowercase().as_str() {
+            "hardbounce" | "hard_bounce" => Bounce::deserialize(&details).map(EventKind::HardBounce),
+            "softbounce" | "soft_bounce" => Bounce::deserialize(&details).map(EventKind::SoftBounce),
+            "email_open" | "open" => Open::deserialize(&details).map(EventKind::Open),
+            "email_link_click" | "link_click" | "click" => Click::deserialize(&details).map(EventKind::Click),
+            "feedback_loop" | "spam_complaint" | "spam" => SpamComplaint::deserialize(&details).map(EventKind::SpamComplaint),
+            "email_delivered" | "delivered" | "delivery" => Delivery::deserialize(&details).map(EventKind::Delivery),
+            _ => return EventKind::Other { name: name.to_string(), details },
+        };
+        kind.unwrap_or_else(|_| EventKind::Other { name: name.to_string(), details })
+    }
+
+    /// Whether the event is a hard bounce or a spam complaint, after which ZeptoMail stops sending
+    /// to the address.
+    pub fn is_permanent_failure(&self) -> bool {
+        matches!(self, EventKind::HardBounce(_) | EventKind::SpamComplaint(_))
+    }
+}
+
+/// The details of a hard or soft bounce.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bounce {
+    #[serde(alias = "bounced_recipient")]
+    pub recipient: Option<String>,
+    pub reason: Option<String>,
+    //The response of the recipient's server, e.g. `550 5.1.1 User unknown`.
+    pub diagnostic_message: Option<String>,
+    pub time: Option<String>,
+    //Details this crate does not model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The details of an email open.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Open {
+    #[serde(alias = "email_address")]
+    pub recipient: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The details of a link click.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Click {
+    #[serde(alias = "email_address")]
+    pub recipient: Option<String>,
+    #[serde(alias = "clicked_link")]
+    pub link: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The details of a spam complaint.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpamComplaint {
+    #[serde(alias = "email_address", alias = "complained_recipient")]
+    pub recipient: Option<String>,
+    pub time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The details of a delivery to the recipient's server.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Delivery {
+    #[serde(alias = "email_address", alias = "delivered_recipient")]
+    pub recipient: Option<String>,
+    pub time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The email an event is about, as ZeptoMail reports it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmailInfo {
+    pub email_reference: Option<String>,
+    pub client_reference: Option<String>,
+    pub subject: Option<String>,
+    pub from: Option<EmailAddress>,
+    #[serde(default)]
+    pub to: Vec<Recipient>,
+    pub bounce_address: Option<String>,
+    pub processed_time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
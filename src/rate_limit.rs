@@ -0,0 +1,205 @@
+//! ## Rate Limiting
+//! This module contains a client-side token-bucket rate limiter, to stay within ZeptoMail's
+//! per-account send limits instead of running into throttling errors.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::ZeptoMailError;
+
+/// What the client does when the rate limiter has no capacity left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitMode {
+    /// Wait until there is capacity.
+    #[default]
+    Wait,
+    /// Fail immediately with [`ZeptoMailError::RateLimited`], for callers that prefer to shed load.
+    FailFast,
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    //Tokens added per second.
+    refill_rate: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, period: Duration) -> Self {
+        let capacity: f64 = f64::from(capacity.max(1));
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            refill_rate: capacity / period.as_secs_f64(),
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed: f64 = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    //How long until `amount` tokens can be taken. Requests larger than the capacity only need a
+    //full bucket, and leave the bucket in debt.
+    fn wait_time(&self, amount: f64) -> Duration {
+        let needed: f64 = amount.min(self.capacity) - self.tokens;
+        if needed <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(needed / self.refill_rate)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Buckets {
+    requests: Option<TokenBucket>,
+    recipients: Option<TokenBucket>,
+}
+
+impl Buckets {
+    //Takes the tokens if both buckets have capacity, or returns how long to wait.
+    fn take(&mut self, recipients: u32) -> Result<(), Duration> {
+        let now: Instant = Instant::now();
+        let recipients: f64 = f64::from(recipients);
+
+        let mut wait: Duration = Duration::ZERO;
+        if let Some(bucket) = self.requests.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_time(1.0));
+        }
+        if let Some(bucket) = self.recipients.as_mut().filter(|_| recipients > 0.0) {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_time(recipients));
+        }
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+
+        if let Some(bucket) = self.requests.as_mut() {
+            bucket.tokens -= 1.0;
+        }
+        if let Some(bucket) = self.recipients.as_mut() {
+            bucket.tokens -= recipients;
+        }
+        Ok(())
+    }
+}
+
+/// A token-bucket rate limiter for requests per second and recipients per minute.
+///
+/// Clones share the same buckets, and so do clones of a [`ZeptoMailClient`](crate::ZeptoMailClient)
+/// it is attached to with [`ZeptoMailClientBuilder::rate_limiter`](crate::ZeptoMailClientBuilder::rate_limiter).
+/// The same limiter can also be attached to several clients that send from the same account.
+///
+/// Setting a limit returns a limiter with buckets of its own, so limits set on a clone do not
+/// affect the limiter it was cloned from.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use zeptomail_rs::{ZeptoMailClient, Region, RateLimiter};
+///
+/// let limiter = RateLimiter::new()
+///     .requests_per_second(10)
+///     .recipients_per_minute(1_000);
+///
+/// let client = ZeptoMailClient::builder("your_api_key", Region::Eu)
+///     .rate_limiter(limiter)
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// Limits set on a clone only apply to the clone:
+///
+/// ```rust
+/// use zeptomail_rs::RateLimiter;
+///
+/// let limiter = RateLimiter::new().requests_per_second(100);
+/// let strict = limiter.clone().requests_per_second(1);
+///
+/// assert!(strict.try_acquire(1).is_ok());
+/// assert!(strict.try_acquire(1).is_err());
+/// assert!(limiter.try_acquire(1).is_ok());
+/// assert!(limiter.try_acquire(1).is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<Buckets>>,
+    mode: RateLimitMode,
+}
+
+impl RateLimiter {
+    /// A limiter without limits. Add them with [`requests_per_second`](Self::requests_per_second)
+    /// and [`recipients_per_minute`](Self::recipients_per_minute).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows at most `limit` requests per second, in bursts of up to `limit` requests.
+    pub fn requests_per_second(self, limit: u32) -> Self {
+        let mut buckets: Buckets = self.buckets().clone();
+        buckets.requests = Some(TokenBucket::new(limit, Duration::from_secs(1)));
+        self.with_buckets(buckets)
+    }
+
+    /// Allows at most `limit` recipients per minute, counting `to`, `cc` and `bcc` addresses.
+    pub fn recipients_per_minute(self, limit: u32) -> Self {
+        let mut buckets: Buckets = self.buckets().clone();
+        buckets.recipients = Some(TokenBucket::new(limit, Duration::from_secs(60)));
+        self.with_buckets(buckets)
+    }
+
+    /// Sets what happens when there is no capacity left. Defaults to [`RateLimitMode::Wait`].
+    pub fn mode(mut self, mode: RateLimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Waits until a request to `recipients` recipients fits in the limits, and takes the capacity.
+    pub async fn acquire(&self, recipients: u32) {
+        loop {
+            let wait: Duration = match self.buckets().take(recipients) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes the capacity for a request to `recipients` recipients if it fits in the limits right now.
+    ///
+    /// Returns [`ZeptoMailError::RateLimited`] with the time until there is capacity otherwise.
+    pub fn try_acquire(&self, recipients: u32) -> Result<(), ZeptoMailError> {
+        self.buckets()
+            .take(recipients)
+            .map_err(|retry_after| ZeptoMailError::RateLimited { retry_after })
+    }
+
+    /// Acquires capacity according to the limiter's mode.
+    pub(crate) async fn acquire_with_mode(&self, recipients: u32) -> Result<(), ZeptoMailError> {
+        match self.mode {
+            RateLimitMode::Wait => {
+                self.acquire(recipients).await;
+                Ok(())
+            }
+            RateLimitMode::FailFast => self.try_acquire(recipients),
+        }
+    }
+
+    //A limiter with `buckets`, not shared with any clone of `self`.
+    fn with_buckets(self, buckets: Buckets) -> Self {
+        RateLimiter {
+            buckets: Arc::new(Mutex::new(buckets)),
+            mode: self.mode,
+        }
+    }
+
+    fn buckets(&self) -> MutexGuard<'_, Buckets> {
+        self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,103 @@
+//! ## Region
+//! ZeptoMail runs a separate data center per region, and an account only exists in the data center
+//! it was created in. This module maps each data center to the hosts the client talks to.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The ZeptoMail data center an account is hosted in.
+///
+/// The region decides which API host requests are sent to. Using the wrong region results in
+/// authentication failures, since the API key is only known to the account's own data center.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    /// United States (`zeptomail.com`)
+    Us,
+    /// Europe (`zeptomail.eu`)
+    Eu,
+    /// India (`zeptomail.in`)
+    In,
+    /// Australia (`zeptomail.com.au`)
+    Au,
+    /// Japan (`zeptomail.jp`)
+    Jp,
+    /// Canada (`zeptomail.ca`)
+    Ca,
+    /// Saudi Arabia (`zeptomail.sa`)
+    Sa,
+    /// China (`zeptomail.com.cn`)
+    Cn,
+}
+
+impl Region {
+    /// Every data center ZeptoMail operates.
+    pub const ALL: [Region; 8] = [
+        Region::Us,
+        Region::Eu,
+        Region::In,
+        Region::Au,
+        Region::Jp,
+        Region::Ca,
+        Region::Sa,
+        Region::Cn,
+    ];
+
+    /// The top level domain of the data center, e.g. `zeptomail.eu`.
+    pub fn domain(&self) -> &'static str {
+        match self {
+            Region::Us => "zeptomail.com",
+            Region::Eu => "zeptomail.eu",
+            Region::In => "zeptomail.in",
+            Region::Au => "zeptomail.com.au",
+            Region::Jp => "zeptomail.jp",
+            Region::Ca => "zeptomail.ca",
+            Region::Sa => "zeptomail.sa",
+            Region::Cn => "zeptomail.com.cn",
+        }
+    }
+
+    /// The base URL of the email sending and template APIs.
+    pub fn api_url(&self) -> String {
+        format!("https://api.{}/v1.1", self.domain())
+    }
+
+    /// The base URL of the file cache upload API.
+    ///
+    /// ZeptoMail currently serves uploads from the same host as the sending API, but the
+    /// client keeps both URLs separate so they can be overridden independently.
+    pub fn upload_url(&self) -> String {
+        format!("https://api.{}/v1.1", self.domain())
+    }
+
+    /// The short region code, e.g. `"eu"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Region::Us => "us",
+            Region::Eu => "eu",
+            Region::In => "in",
+            Region::Au => "au",
+            Region::Jp => "jp",
+            Region::Ca => "ca",
+            Region::Sa => "sa",
+            Region::Cn => "cn",
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    /// Parses a region code such as `"us"` or `"EU"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Region::ALL
+            .into_iter()
+            .find(|region| region.code().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Unknown ZeptoMail region: {}", s))
+    }
+}
@@ -0,0 +1,219 @@
+//! ## Rendering
+//! This module contains a local renderer for ZeptoMail's `{{placeholder}}` merge tags, to preview
+//! and snapshot-test what every recipient of a batch email will receive.
+//!
+//! A placeholder is the name of a merge field between double braces, e.g. `{{name}}`. Fields of
+//! nested objects and elements of arrays are reached with dotted paths, e.g. `{{order.items.0.name}}`.
+//! Whitespace inside the braces is ignored. Placeholders without a value are replaced with nothing
+//! and reported as unresolved.
+//!
+//! Only plain placeholders are supported:
+//! * Section and loop tags such as `{{#each items}}` and `{{/each}}` are copied to the output as they
+//!   are. Placeholders between them are looked up at the top level of the merge info, so fields of the
+//!   loop items are reported as unresolved.
+//! * Values are inserted into `htmlbody` without HTML escaping.
+//!
+//! Emails that loop over arrays or insert values with HTML special characters can therefore render
+//! differently on ZeptoMail. Check those with a test send rather than a local snapshot.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{BatchEmailRequest, EmailAddress, MergeInfo};
+
+/// A placeholder that had no value in the merge info.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedPlaceholder {
+    //The field the placeholder is in: `subject`, `htmlbody` or `textbody`.
+    pub field: String,
+    //The placeholder without braces, e.g. `order.id`.
+    pub name: String,
+}
+
+/// The result of rendering a single text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub output: String,
+    //The names of the placeholders without a value, in order of first appearance.
+    pub unresolved: Vec<String>,
+}
+
+/// An email as one recipient of a batch receives it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderedEmail {
+    pub to: EmailAddress,
+    pub subject: String,
+    pub htmlbody: Option<String>,
+    pub textbody: Option<String>,
+    pub unresolved: Vec<UnresolvedPlaceholder>,
+}
+
+impl RenderedEmail {
+    /// Whether every placeholder had a value.
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// Replaces the placeholders in `template` with the values in `merge_info`.
+///
+/// Strings are inserted as they are, numbers and booleans in their JSON notation, and objects and
+/// arrays as JSON. `null` counts as a value and renders as nothing.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use zeptomail_rs::MergeData;
+/// use zeptomail_rs::render::render;
+///
+/// let merge_info = json!({ "name": "Jane", "order": { "id": 1042 } }).to_merge_info().unwrap();
+/// let rendered = render("Hi {{ name }}, order {{order.id}} ships to {{city}}.", &merge_info);
+///
+/// assert_eq!(rendered.output, "Hi Jane, order 1042 ships to .");
+/// assert_eq!(rendered.unresolved, vec!["city".to_string()]);
+/// ```
+///
+/// Loops are not supported, and values are not escaped:
+///
+/// ```rust
+/// use serde_json::json;
+/// use zeptomail_rs::MergeData;
+/// use zeptomail_rs::render::render;
+///
+/// let merge_info = json!({ "items": [{ "name": "Keyboard" }], "note": "<b>Fast</b> & free" })
+///     .to_merge_info()
+///     .unwrap();
+/// let rendered = render("<ul>{{#each items}}<li>{{name}}</li>{{/each}}</ul>{{note}}", &merge_info);
+///
+/// assert_eq!(rendered.output, "<ul>{{#each items}}<li></li>{{/each}}</ul><b>Fast</b> & free");
+/// assert_eq!(rendered.unresolved, vec!["name".to_string()]);
+/// ```
+pub fn render(template: &str, merge_info: &MergeInfo) -> Rendered {
+    let mut output: String = String::with_capacity(template.len());
+    let mut unresolved: Vec<String> = Vec::new();
+    let mut rest: &str = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name: &str = rest[start + 2..start + 2 + length].trim();
+
+        output.push_str(&rest[..start]);
+        if is_placeholder_name(name) {
+            match lookup(merge_info, name) {
+                Some(value) => push_value(&mut output, value),
+                None if !unresolved.iter().any(|unresolved| unresolved == name) => {
+                    unresolved.push(name.to_string());
+                }
+                None => {}
+            }
+        } else {
+            //Not a merge tag, e.g. `{{ }}` in inline CSS or code samples.
+            output.push_str(&rest[start..start + 4 + length]);
+        }
+        rest = &rest[start + 4 + length..];
+    }
+    output.push_str(rest);
+
+    Rendered { output, unresolved }
+}
+
+/// Renders the subject and bodies of `batch_email_request` for every recipient, in order.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{BatchEmailRequest, Recipient};
+/// use zeptomail_rs::render::render_batch_email;
+///
+/// let batch_email_request = BatchEmailRequest::builder()
+///     .sender("sender@example.com")
+///     .to(Recipient::new("jane@example.com").with_merge_field("name", "Jane"))
+///     .to(Recipient::new("john@example.com").with_merge_field("nmae", "John"))
+///     .subject("Hello {{name}}")
+///     .html("<p>Hello {{name}}</p>")
+///     .build()
+///     .unwrap();
+///
+/// let emails = render_batch_email(&batch_email_request);
+///
+/// assert_eq!(emails[0].subject, "Hello Jane");
+/// assert_eq!(emails[0].htmlbody.as_deref(), Some("<p>Hello Jane</p>"));
+/// assert!(!emails[1].is_complete());
+/// assert_eq!(emails[1].unresolved[0].name, "name");
+/// ```
+pub fn render_batch_email(batch_email_request: &BatchEmailRequest) -> Vec<RenderedEmail> {
+    let empty: MergeInfo = MergeInfo::new();
+
+    batch_email_request
+        .recipients
+        .iter()
+        .map(|recipient| {
+            let merge_info: &MergeInfo = recipient.merge_info.as_ref().unwrap_or(&empty);
+            render_email(
+                recipient.email_address.clone(),
+                &batch_email_request.subject,
+                batch_email_request.htmlbody.as_deref(),
+                batch_email_request.textbody.as_deref(),
+                merge_info,
+            )
+        })
+        .collect()
+}
+
+/// Renders a subject and bodies for a single recipient.
+pub fn render_email(
+    to: EmailAddress,
+    subject: &str,
+    htmlbody: Option<&str>,
+    textbody: Option<&str>,
+    merge_info: &MergeInfo
+) -> RenderedEmail {
+    let mut unresolved: Vec<UnresolvedPlaceholder> = Vec::new();
+    let mut render_field = |field: &str, template: &str| -> String {
+        let rendered: Rendered = render(template, merge_info);
+        unresolved.extend(rendered.unresolved.into_iter().map(|name| UnresolvedPlaceholder {
+            field: field.to_string(),
+            name,
+        }));
+        rendered.output
+    };
+
+    RenderedEmail {
+        to,
+        subject: render_field("subject", subject),
+        htmlbody: htmlbody.map(|htmlbody| render_field("htmlbody", htmlbody)),
+        textbody: textbody.map(|textbody| render_field("textbody", textbody)),
+        unresolved,
+    }
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|segment| {
+            !segment.is_empty() && segment.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+fn lookup<'a>(merge_info: &'a MergeInfo, name: &str) -> Option<&'a Value> {
+    let mut segments = name.split('.');
+    let mut value: &Value = merge_info.get(segments.next()?)?;
+    for segment in segments {
+        value = match value {
+            Value::Object(object) => object.get(segment)?,
+            Value::Array(array) => array.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn push_value(output: &mut String, value: &Value) {
+    match value {
+        Value::Null => {}
+        Value::String(string) => output.push_str(string),
+        other => output.push_str(&other.to_string()),
+    }
+}
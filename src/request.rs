@@ -0,0 +1,269 @@
+//! ## Request Pipeline
+//! This module contains the request executor every endpoint goes through.
+//!
+//! An endpoint only declares its method, path and types by implementing [`Endpoint`].
+//! [`ZeptoMailClient::execute`] takes care of validation, the URL, authentication, content negotiation,
+//! the response-size limit, request-ID capture, rate limiting, retries and mapping failures into [`ZeptoMailError`].
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::{Method, Url};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+
+use crate::models::api_failure::{body_snippet, ApiErrorBody};
+use crate::retry;
+use crate::transport::{BodyStream, HttpRequest, HttpResponse, RequestBody};
+use crate::validation::{ValidationErrors, ValidationRules};
+use crate::{ApiResponse, FileUploadResponse, ZeptoMailClient, ZeptoMailError};
+
+//Response headers that may carry the ID ZeptoMail assigned to a request.
+const REQUEST_ID_HEADERS: [&str; 2] = ["x-request-id", "request_id"];
+
+/// Which of the client's base URLs an endpoint lives under.
+pub(crate) enum BaseUrl {
+    Api,
+    Upload,
+}
+
+/// The body an endpoint sends.
+pub(crate) enum Payload {
+    Empty,
+    Json(Vec<u8>),
+    Raw {
+        content_type: HeaderValue,
+        body: Bytes,
+    },
+    Stream {
+        content_type: HeaderValue,
+        stream: BodyStream,
+        length: u64,
+    },
+}
+
+impl Payload {
+    pub(crate) fn json<T: Serialize + ?Sized>(value: &T) -> Result<Self, ZeptoMailError> {
+        Ok(Payload::Json(serde_json::to_vec(value)?))
+    }
+}
+
+/// Details about how a response was obtained that are not part of the response body.
+pub(crate) trait ResponseMetadata {
+    fn record_attempts(&mut self, _attempts: u32) {}
+}
+
+impl ResponseMetadata for ApiResponse {
+    fn record_attempts(&mut self, attempts: u32) {
+        self.attempts = attempts;
+    }
+}
+
+impl ResponseMetadata for FileUploadResponse {
+    fn record_attempts(&mut self, attempts: u32) {
+        self.attempts = attempts;
+    }
+}
+
+/// The response of an endpoint whose body carries nothing of interest, e.g. a deletion.
+#[derive(Deserialize)]
+pub(crate) struct IgnoredResponse(#[allow(dead_code)] IgnoredAny);
+
+impl ResponseMetadata for IgnoredResponse {}
+
+/// A ZeptoMail API endpoint.
+pub(crate) trait Endpoint {
+    /// The type the success response body is parsed into.
+    type Response: DeserializeOwned + ResponseMetadata;
+
+    const METHOD: Method;
+
+    const BASE_URL: BaseUrl = BaseUrl::Api;
+
+    /// The segments of the path relative to the base URL, e.g. `["email", "batch"]`. Every segment is
+    /// percent-encoded, so a `/` in a segment does not start a new one.
+    fn path(&self) -> Vec<&str>;
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Ok(Payload::Empty)
+    }
+
+    /// Whether [`payload`](Self::payload) can be called again to retry the request. Streams supplied
+    /// by the caller can only be read once.
+    fn is_replayable(&self) -> bool {
+        true
+    }
+
+    /// Checks the request before it is sent, unless validation is turned off on the client.
+    fn validate_request(&self, _rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+
+    /// The number of addresses the request sends to, for the recipients-per-minute rate limit.
+    fn recipient_count(&self) -> u32 {
+        0
+    }
+}
+
+impl ZeptoMailClient {
+    /// Sends `endpoint` and parses the response, retrying transient failures per the retry policy.
+    pub(crate) async fn execute<E: Endpoint>(
+        &self,
+        endpoint: &E
+    ) -> Result<E::Response, ZeptoMailError> {
+        if self.validate_requests {
+            endpoint.validate_request(&self.validation_rules).map_err(ZeptoMailError::Validation)?;
+        }
+
+        let mut attempt: u32 = 0;
+
+        loop {
+            attempt += 1;
+
+            let (result, retry_after) = self.send_once(endpoint).await;
+            match result {
+                Ok(mut response) => {
+                    response.record_attempts(attempt);
+                    return Ok(response);
+                }
+                Err(error) => {
+                    if attempt >= self.retry_policy.max_attempts || !error.is_retryable() || !endpoint.is_replayable() {
+                        if attempt == 1 {
+                            return Err(error);
+                        }
+                        return Err(ZeptoMailError::Retried { attempts: attempt, source: Box::new(error) });
+                    }
+                    tokio::time::sleep(self.retry_policy.delay(attempt, retry_after)).await;
+                }
+            }
+        }
+    }
+
+    //Makes one attempt, returning the parsed response and the delay a `Retry-After` header asked for.
+    async fn send_once<E: Endpoint>(
+        &self,
+        endpoint: &E
+    ) -> (Result<E::Response, ZeptoMailError>, Option<Duration>) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if let Err(error) = rate_limiter.acquire_with_mode(endpoint.recipient_count()).await {
+                return (Err(error), None);
+            }
+        }
+
+        let request: HttpRequest = match self.build_request(endpoint) {
+            Ok(request) => request,
+            Err(error) => return (Err(error), None),
+        };
+        match self.transport.send(request).await {
+            Ok(response) => {
+                let retry_after: Option<Duration> = retry::retry_after(&response.headers);
+                (self.parse_response::<E::Response>(response), retry_after)
+            }
+            Err(error) => (Err(error), None),
+        }
+    }
+
+    fn build_request<E: Endpoint>(
+        &self,
+        endpoint: &E
+    ) -> Result<HttpRequest, ZeptoMailError> {
+        let base_url: &str = match E::BASE_URL {
+            BaseUrl::Api => &self.base_url,
+            BaseUrl::Upload => &self.upload_url,
+        };
+
+        let mut url: Url = Url::parse(base_url).map_err(|e| {
+            ZeptoMailError::InvalidConfiguration(format!("Invalid base URL {}: {}", base_url, e))
+        })?;
+        url.path_segments_mut()
+            .map_err(|_| ZeptoMailError::InvalidConfiguration(format!("Invalid base URL {}", base_url)))?
+            .pop_if_empty()
+            .extend(endpoint.path());
+
+        let query: Vec<(&'static str, String)> = endpoint.query();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(AUTHORIZATION, self.auth_header.clone());
+
+        let body: RequestBody = match endpoint.payload()? {
+            Payload::Empty => RequestBody::Empty,
+            Payload::Json(json) => {
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                RequestBody::Bytes(json.into())
+            }
+            Payload::Raw { content_type, body } => {
+                headers.insert(CONTENT_TYPE, content_type);
+                RequestBody::Bytes(body)
+            }
+            Payload::Stream { content_type, stream, length } => {
+                headers.insert(CONTENT_TYPE, content_type);
+                headers.insert(CONTENT_LENGTH, HeaderValue::from(length));
+                RequestBody::Stream { stream, length }
+            }
+        };
+
+        Ok(HttpRequest {
+            method: E::METHOD,
+            url: url.into(),
+            headers,
+            body,
+            max_response_bytes: Some(self.max_response_bytes),
+        })
+    }
+
+    fn parse_response<T: DeserializeOwned>(
+        &self,
+        response: HttpResponse
+    ) -> Result<T, ZeptoMailError> {
+        if response.body.len() > self.max_response_bytes {
+            return Err(ZeptoMailError::ResponseTooLarge { limit: self.max_response_bytes });
+        }
+
+        if response.status.is_success() {
+            //Some endpoints, e.g. deletions, answer with an empty body.
+            let body: &[u8] = if response.body.is_empty() { b"null" } else { &response.body };
+            return serde_json::from_slice(body).map_err(|source| {
+                ZeptoMailError::DeserializationError {
+                    status: response.status,
+                    source,
+                    body_snippet: body_snippet(&response.body),
+                }
+            });
+        }
+
+        // Gateways and load balancers answer with HTML pages or empty bodies, so the raw body is
+        // kept when the response is not a ZeptoMail error.
+        let mut api_error = match serde_json::from_slice::<ApiErrorBody>(&response.body) {
+            Ok(body) => body.into_inner(),
+            Err(_) => {
+                return Err(ZeptoMailError::HttpError {
+                    status: response.status,
+                    body_snippet: body_snippet(&response.body),
+                    headers: response.headers,
+                });
+            }
+        };
+        api_error.http_status = Some(response.status.as_u16());
+        if api_error.request_id.is_none() {
+            api_error.request_id = header_request_id(&response.headers);
+        }
+        Err(ZeptoMailError::ApiError(api_error))
+    }
+}
+
+fn header_request_id(headers: &HeaderMap) -> Option<String> {
+    REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
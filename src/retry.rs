@@ -0,0 +1,142 @@
+//! ## Retries
+//! This module contains the retry policy the client applies to transient failures.
+//!
+//! A request is retried when ZeptoMail throttles it (HTTP 429), fails with a server error (HTTP 5xx)
+//! or the connection fails, unless the API error code marks the failure as fatal. See
+//! [`ZeptoMailError::is_retryable`](crate::ZeptoMailError::is_retryable) for the exact rules.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// How often and how long the client waits before retrying a failed request.
+///
+/// Delays grow exponentially from `base_delay` and are capped at `max_delay`. With jitter enabled,
+/// each delay is drawn uniformly between zero and the exponential delay, so clients that failed at
+/// the same time do not retry at the same time.
+///
+/// Sending an email is not idempotent: a request that timed out may still have been accepted, and
+/// retrying it can deliver the email twice. Clients therefore do not retry unless a policy is set
+/// with [`ZeptoMailClientBuilder::retry_policy`](crate::ZeptoMailClientBuilder::retry_policy).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use zeptomail_rs::{ZeptoMailClient, Region, RetryPolicy};
+///
+/// let client = ZeptoMailClient::builder("your_api_key", Region::Eu)
+///     .retry_policy(RetryPolicy::new().max_attempts(5).max_delay(Duration::from_secs(10)))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    respect_retry_after: bool,
+}
+
+impl RetryPolicy {
+    /// The recommended policy: 3 attempts, starting at 500ms and capped at 30 seconds, with jitter,
+    /// honouring `Retry-After`.
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+
+    /// A policy that never retries. This is the client's default.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the total number of attempts, including the first one. Values below 1 are treated as 1.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the longest delay between two attempts, including delays asked for with `Retry-After`.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Enables or disables jitter.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Whether to wait as long as a `Retry-After` response header asks for.
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// The delay before the attempt following `attempt`, where the first attempt is 1.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponent: u32 = attempt.saturating_sub(1).min(31);
+        let delay: Duration = self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter {
+            delay.mul_f64(random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses a `Retry-After` header, given either in seconds or as an HTTP date. A date in the past
+/// means no delay.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value: &str = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date: SystemTime = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+//A random number in [0, 1), seeded by the standard library's per-process random hasher keys.
+fn random_fraction() -> f64 {
+    let nanos: u128 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(nanos);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,375 @@
+//! ## Suppression Guard
+//! This module contains the local suppression check the client can run before it sends an email.
+//!
+//! With a [`SuppressionStore`] set on the client, [`ZeptoMailClient::send_email`], the template sends
+//! and the batch sends look up every recipient, CC and BCC before the request goes out. Depending on
+//! the [`SuppressionPolicy`], suppressed addresses are dropped from the request or the send is rejected
+//! with [`ZeptoMailError::Suppressed`]. Dropped addresses are reported in
+//! [`ApiResponse::suppressed`](crate::ApiResponse::suppressed).
+//!
+//! [`WebhookSuppressionStore`] fills itself from hard bounce and spam complaint webhook events, so
+//! addresses stop being sent to as soon as ZeptoMail reports them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+
+use crate::models::suppression::{address_domain, normalize_address};
+use crate::models::webhook::{Bounce, SpamComplaint};
+use crate::request::Endpoint;
+use crate::webhooks::{HandlerError, WebhookHandler};
+use crate::{
+    ApiResponse,
+    BatchEmailRequest,
+    BatchTemplateEmailRequest,
+    EmailRequest,
+    EventKind,
+    Recipient,
+    Suppression,
+    SuppressionReason,
+    SuppressionType,
+    TemplateEmailRequest,
+    WebhookEvent,
+    ZeptoMailClient,
+    ZeptoMailError,
+};
+
+/// Where the client looks up whether an address must not be sent to.
+#[async_trait]
+pub trait SuppressionStore: Send + Sync {
+    /// Whether `address`, or its domain, is suppressed.
+    async fn is_suppressed(&self, address: &str) -> Result<bool, ZeptoMailError>;
+
+    /// Whether each of `addresses` is suppressed, in the same order.
+    ///
+    /// The client calls this once per send. The default looks the addresses up one at a time, so
+    /// stores backed by a database should look them up in a single query instead.
+    async fn are_suppressed(&self, addresses: &[String]) -> Result<Vec<bool>, ZeptoMailError> {
+        let mut suppressed: Vec<bool> = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            suppressed.push(self.is_suppressed(address).await?);
+        }
+        Ok(suppressed)
+    }
+
+    async fn suppress(&self, suppression: Suppression) -> Result<(), ZeptoMailError>;
+
+    async fn remove(&self, suppression: &Suppression) -> Result<(), ZeptoMailError>;
+}
+
+/// What the client does when a request has suppressed addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SuppressionPolicy {
+    /// Drops the suppressed addresses and sends to the others. The send is rejected only if no
+    /// recipient is left.
+    #[default]
+    Filter,
+    /// Rejects the whole send.
+    Reject,
+}
+
+/// A [`SuppressionStore`] that keeps suppressions in memory. Clones share the same suppressions.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::Suppression;
+/// use zeptomail_rs::suppression::guard::{MemorySuppressionStore, SuppressionStore};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let store = MemorySuppressionStore::new();
+///     store.suppress(Suppression::domain("example.org")).await.unwrap();
+///
+///     assert!(store.is_suppressed("Jane@Example.org").await.unwrap());
+///     assert!(!store.is_suppressed("jane@example.com").await.unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemorySuppressionStore {
+    //By type and normalized value.
+    entries: Arc<Mutex<HashMap<(SuppressionType, String), Suppression>>>,
+}
+
+impl MemorySuppressionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every suppression in the store, in no particular order.
+    pub fn suppressions(&self) -> Vec<Suppression> {
+        self.entries().values().cloned().collect()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<(SuppressionType, String), Suppression>> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl SuppressionStore for MemorySuppressionStore {
+    async fn is_suppressed(&self, address: &str) -> Result<bool, ZeptoMailError> {
+        Ok(contains(&self.entries(), address))
+    }
+
+    async fn are_suppressed(&self, addresses: &[String]) -> Result<Vec<bool>, ZeptoMailError> {
+        let entries = self.entries();
+        Ok(addresses.iter().map(|address| contains(&entries, address)).collect())
+    }
+
+    async fn suppress(&self, suppression: Suppression) -> Result<(), ZeptoMailError> {
+        self.entries().insert((suppression.kind, normalize_address(&suppression.value)), suppression);
+        Ok(())
+    }
+
+    async fn remove(&self, suppression: &Suppression) -> Result<(), ZeptoMailError> {
+        self.entries().remove(&(suppression.kind, normalize_address(&suppression.value)));
+        Ok(())
+    }
+}
+
+//Whether `address` or its domain is in `entries`.
+fn contains(entries: &HashMap<(SuppressionType, String), Suppression>, address: &str) -> bool {
+    let address: String = normalize_address(address);
+    let domain: Option<&str> = address_domain(&address);
+
+    entries.contains_key(&(SuppressionType::Email, address.clone()))
+        || domain.is_some_and(|domain| entries.contains_key(&(SuppressionType::Domain, domain.to_string())))
+}
+
+/// A [`SuppressionStore`] that suppresses addresses when webhook events report a hard bounce or a
+/// spam complaint. Clones share the same store.
+///
+/// The store is a [`WebhookHandler`], so it can be given to a
+/// [`WebhookReceiver`](crate::webhooks::WebhookReceiver) directly. A custom handler can feed it with
+/// [`record`](Self::record) instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use zeptomail_rs::{ZeptoMailClient, Region};
+/// use zeptomail_rs::suppression::guard::WebhookSuppressionStore;
+/// use zeptomail_rs::webhooks::{WebhookReceiver, WebhookVerifier};
+///
+/// let store = WebhookSuppressionStore::new();
+///
+/// let client = ZeptoMailClient::builder("your_api_key", Region::Eu)
+///     .suppression_store(store.clone())
+///     .build()
+///     .unwrap();
+///
+/// let receiver = WebhookReceiver::new(store, WebhookVerifier::new("your_webhook_secret"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WebhookSuppressionStore<S = MemorySuppressionStore> {
+    store: S,
+}
+
+impl WebhookSuppressionStore {
+    /// A store that keeps the suppressions in memory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: SuppressionStore> WebhookSuppressionStore<S> {
+    /// A store that keeps the suppressions in `store`, e.g. a database.
+    pub fn with_store(store: S) -> Self {
+        WebhookSuppressionStore { store }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Suppresses the recipient of `event` if it is a hard bounce or a spam complaint. Returns
+    /// whether an address was suppressed.
+    pub async fn record(&self, event: &WebhookEvent) -> Result<bool, ZeptoMailError> {
+        let reason: SuppressionReason = match &event.kind {
+            EventKind::HardBounce(_) => SuppressionReason::HardBounce,
+            EventKind::SpamComplaint(_) => SuppressionReason::SpamComplaint,
+            _ => return Ok(false),
+        };
+        let Some(recipient) = event.recipient() else {
+            return Ok(false);
+        };
+
+        let mut suppression: Suppression = Suppression::email(recipient);
+        suppression.reason = Some(reason);
+        self.store.suppress(suppression).await?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl<S: SuppressionStore> SuppressionStore for WebhookSuppressionStore<S> {
+    async fn is_suppressed(&self, address: &str) -> Result<bool, ZeptoMailError> {
+        self.store.is_suppressed(address).await
+    }
+
+    async fn are_suppressed(&self, addresses: &[String]) -> Result<Vec<bool>, ZeptoMailError> {
+        self.store.are_suppressed(addresses).await
+    }
+
+    async fn suppress(&self, suppression: Suppression) -> Result<(), ZeptoMailError> {
+        self.store.suppress(suppression).await
+    }
+
+    async fn remove(&self, suppression: &Suppression) -> Result<(), ZeptoMailError> {
+        self.store.remove(suppression).await
+    }
+}
+
+#[async_trait]
+impl<S: SuppressionStore + 'static> WebhookHandler for WebhookSuppressionStore<S> {
+    async fn on_hard_bounce(&self, event: &WebhookEvent, _bounce: &Bounce) -> Result<(), HandlerError> {
+        self.record(event).await?;
+        Ok(())
+    }
+
+    async fn on_spam_complaint(
+        &self,
+        event: &WebhookEvent,
+        _complaint: &SpamComplaint
+    ) -> Result<(), HandlerError> {
+        self.record(event).await?;
+        Ok(())
+    }
+}
+
+/// A request whose recipients can be checked against a [`SuppressionStore`].
+pub(crate) trait SuppressionTarget {
+    /// The lists of recipients, starting with the one that must not end up empty.
+    fn recipient_lists(&mut self) -> Vec<&mut Vec<Recipient>>;
+
+    /// Unsets the optional lists that are empty after filtering.
+    fn clear_empty_lists(&mut self);
+}
+
+impl SuppressionTarget for EmailRequest {
+    fn recipient_lists(&mut self) -> Vec<&mut Vec<Recipient>> {
+        with_copies(&mut self.recipients, [&mut self.carbon_copy, &mut self.blind_carbon_copy])
+    }
+
+    fn clear_empty_lists(&mut self) {
+        clear_empty_copies([&mut self.carbon_copy, &mut self.blind_carbon_copy]);
+    }
+}
+
+impl SuppressionTarget for BatchEmailRequest {
+    fn recipient_lists(&mut self) -> Vec<&mut Vec<Recipient>> {
+        with_copies(&mut self.recipients, [&mut self.carbon_copy, &mut self.blind_carbon_copy])
+    }
+
+    fn clear_empty_lists(&mut self) {
+        clear_empty_copies([&mut self.carbon_copy, &mut self.blind_carbon_copy]);
+    }
+}
+
+impl SuppressionTarget for TemplateEmailRequest {
+    fn recipient_lists(&mut self) -> Vec<&mut Vec<Recipient>> {
+        with_copies(&mut self.recipients, [&mut self.carbon_copy, &mut self.blind_carbon_copy])
+    }
+
+    fn clear_empty_lists(&mut self) {
+        clear_empty_copies([&mut self.carbon_copy, &mut self.blind_carbon_copy]);
+    }
+}
+
+impl SuppressionTarget for BatchTemplateEmailRequest {
+    fn recipient_lists(&mut self) -> Vec<&mut Vec<Recipient>> {
+        with_copies(&mut self.recipients, [&mut self.carbon_copy, &mut self.blind_carbon_copy])
+    }
+
+    fn clear_empty_lists(&mut self) {
+        clear_empty_copies([&mut self.carbon_copy, &mut self.blind_carbon_copy]);
+    }
+}
+
+//The recipients followed by the CC and BCC lists that are set.
+fn with_copies<'a>(
+    recipients: &'a mut Vec<Recipient>,
+    copies: [&'a mut Option<Vec<Recipient>>; 2]
+) -> Vec<&'a mut Vec<Recipient>> {
+    let mut lists: Vec<&mut Vec<Recipient>> = vec![recipients];
+    lists.extend(copies.into_iter().filter_map(Option::as_mut));
+    lists
+}
+
+fn clear_empty_copies(copies: [&mut Option<Vec<Recipient>>; 2]) {
+    for copy in copies {
+        if copy.as_ref().is_some_and(Vec::is_empty) {
+            *copy = None;
+        }
+    }
+}
+
+impl ZeptoMailClient {
+    /// Removes the suppressed addresses from `request` according to the suppression policy and
+    /// returns them.
+    pub(crate) async fn apply_suppressions<R: SuppressionTarget>(
+        &self,
+        request: &mut R
+    ) -> Result<Vec<Recipient>, ZeptoMailError> {
+        let Some(store) = &self.suppression_store else {
+            return Ok(Vec::new());
+        };
+
+        let mut addresses: Vec<String> = request
+            .recipient_lists()
+            .into_iter()
+            .flat_map(|list| list.iter().map(|recipient| normalize_address(&recipient.email_address.address)))
+            .collect();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let results: Vec<bool> = store.are_suppressed(&addresses).await?;
+        if results.len() != addresses.len() {
+            return Err(ZeptoMailError::UnexpectedResponse(format!(
+                "The suppression store returned {} results for {} addresses",
+                results.len(),
+                addresses.len()
+            )));
+        }
+        let suppressed: HashMap<String, bool> = addresses.into_iter().zip(results).collect();
+        let is_suppressed = |recipient: &Recipient| suppressed[&normalize_address(&recipient.email_address.address)];
+
+        let mut dropped: Vec<Recipient> = Vec::new();
+        let mut lists: Vec<&mut Vec<Recipient>> = request.recipient_lists();
+        for list in lists.iter_mut() {
+            dropped.extend(list.iter().filter(|recipient| is_suppressed(recipient)).cloned());
+        }
+        if dropped.is_empty() {
+            return Ok(dropped);
+        }
+
+        let is_required_list_suppressed: bool = !lists[0].is_empty() && lists[0].iter().all(is_suppressed);
+        if self.suppression_policy == SuppressionPolicy::Reject || is_required_list_suppressed {
+            return Err(ZeptoMailError::Suppressed {
+                recipients: dropped.into_iter().map(|recipient| recipient.email_address).collect(),
+            });
+        }
+
+        for list in lists {
+            list.retain(|recipient| !is_suppressed(recipient));
+        }
+        request.clear_empty_lists();
+        Ok(dropped)
+    }
+
+    /// Applies the suppression policy to `request` and sends it. The client validates the request
+    /// with the suppressed addresses already dropped.
+    pub(crate) async fn send_unsuppressed<R>(
+        &self,
+        mut request: R
+    ) -> Result<ApiResponse, ZeptoMailError>
+    where
+        R: SuppressionTarget + Endpoint<Response = ApiResponse>,
+    {
+        let dropped: Vec<Recipient> = self.apply_suppressions(&mut request).await?;
+        let mut response: ApiResponse = self.execute(&request).await?;
+        response.suppressed = dropped.into_iter().map(|recipient| recipient.email_address).collect();
+        Ok(response)
+    }
+}
@@ -0,0 +1,258 @@
+//! ## Suppression List
+//! This module contains the implementation for reading and managing the suppression list using the ZeptoMail API.
+//!
+//! ZeptoMail does not send to suppressed addresses, or to any address of a suppressed domain.
+//! Addresses are suppressed automatically after hard bounces and spam complaints, and can be added
+//! and removed through these endpoints.
+
+use reqwest::Method;
+
+use crate::models::suppression::{SuppressionRequest, SuppressionResponse};
+use crate::request::{Endpoint, IgnoredResponse, Payload};
+use crate::validation::{Validate, ValidationErrors, ValidationRules, ViolationKind};
+use crate::{
+    client::ZeptoMailClient,
+    Suppression,
+    SuppressionListOptions,
+    SuppressionPage,
+    ZeptoMailError,
+};
+
+impl ZeptoMailClient {
+    /// Lists one page of the suppression list.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - A `SuppressionListOptions` with the offset and size of the page, and the type, reason or text to filter by.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(SuppressionPage)` - The entries on the page, with the options for the next page.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, SuppressionListOptions, SuppressionReason};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let mut options = Some(SuppressionListOptions::new().limit(100).reason(SuppressionReason::HardBounce));
+    ///     while let Some(page_options) = options {
+    ///         let page = client.list_suppressions(page_options).await?;
+    ///         for suppression in &page.suppressions {
+    ///             println!("{} ({:?})", suppression.value, suppression.reason);
+    ///         }
+    ///         options = page.next_page();
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_suppressions(
+        &self,
+        options: SuppressionListOptions
+    ) -> Result<SuppressionPage, ZeptoMailError> {
+        let response: SuppressionResponse = self.execute(&ListSuppressions { options: &options }).await?;
+        Ok(SuppressionPage {
+            suppressions: response.data,
+            options,
+        })
+    }
+
+    /// Adds addresses or domains to the suppression list.
+    ///
+    /// # Arguments
+    ///
+    /// * `suppressions` - The addresses and domains to suppress, e.g. `Suppression::email("jane@example.com")`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(Vec<Suppression>)` - The entries ZeptoMail added, if it returns them.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If no entries are given, or an address or domain is invalid.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, Suppression};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     client
+    ///         .add_suppressions(&[Suppression::email("jane@example.com"), Suppression::domain("example.org")])
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn add_suppressions(
+        &self,
+        suppressions: &[Suppression]
+    ) -> Result<Vec<Suppression>, ZeptoMailError> {
+        let response: SuppressionResponse = self.execute(&AddSuppressions { suppressions }).await?;
+        Ok(response.data)
+    }
+
+    /// Removes addresses or domains from the suppression list, so ZeptoMail sends to them again.
+    ///
+    /// # Arguments
+    ///
+    /// * `suppressions` - The addresses and domains to remove.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(())` - If the entries are removed.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If no entries are given, or an address or domain is invalid.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, Suppression};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     client.remove_suppressions(&[Suppression::email("jane@example.com")]).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn remove_suppressions(
+        &self,
+        suppressions: &[Suppression]
+    ) -> Result<(), ZeptoMailError> {
+        let _: IgnoredResponse = self.execute(&RemoveSuppressions { suppressions }).await?;
+        Ok(())
+    }
+}
+
+fn check_suppressions(
+    suppressions: &[Suppression],
+    rules: &ValidationRules
+) -> Result<(), ValidationErrors> {
+    let mut errors: ValidationErrors = ValidationErrors::default();
+    if suppressions.is_empty() {
+        errors.push("suppressions", ViolationKind::Missing);
+    }
+    for (index, suppression) in suppressions.iter().enumerate() {
+        if let Err(suppression_errors) = suppression.validate_with(rules) {
+            for violation in suppression_errors.into_violations() {
+                errors.push(format!("suppressions[{}].{}", index, violation.field), violation.kind);
+            }
+        }
+    }
+    errors.into_result()
+}
+
+struct ListSuppressions<'a> {
+    options: &'a SuppressionListOptions,
+}
+
+impl Endpoint for ListSuppressions<'_> {
+    type Response = SuppressionResponse;
+
+    const METHOD: Method = Method::GET;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["suppressions"]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query: Vec<(&'static str, String)> = vec![
+            ("offset", self.options.offset.to_string()),
+            ("limit", self.options.limit.to_string()),
+        ];
+        if let Some(kind) = self.options.kind {
+            query.push(("type", kind.as_str().to_string()));
+        }
+        if let Some(reason) = self.options.reason {
+            query.push(("reason", reason.as_str().to_string()));
+        }
+        if let Some(search) = &self.options.search {
+            query.push(("search", search.clone()));
+        }
+        query
+    }
+}
+
+struct AddSuppressions<'a> {
+    suppressions: &'a [Suppression],
+}
+
+impl Endpoint for AddSuppressions<'_> {
+    type Response = SuppressionResponse;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["suppressions"]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(&SuppressionRequest { data: self.suppressions })
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        check_suppressions(self.suppressions, rules)
+    }
+}
+
+struct RemoveSuppressions<'a> {
+    suppressions: &'a [Suppression],
+}
+
+impl Endpoint for RemoveSuppressions<'_> {
+    type Response = IgnoredResponse;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["suppressions"]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(&SuppressionRequest { data: self.suppressions })
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        check_suppressions(self.suppressions, rules)
+    }
+}
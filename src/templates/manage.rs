@@ -0,0 +1,439 @@
+//! ## Template Management
+//! This module contains the implementation for managing the templates of a Mail Agent using the ZeptoMail API.
+//!
+//! Templates belong to a Mail Agent and are addressed by its alias, which is shown in the Mail Agent's
+//! settings. With these endpoints, template sources can be kept in version control and synced to
+//! ZeptoMail instead of being edited in the dashboard.
+
+use reqwest::Method;
+
+use crate::models::managed_template::TemplateResponse;
+use crate::request::{Endpoint, IgnoredResponse, Payload};
+use crate::validation::{Validate, ValidationErrors, ValidationRules, ViolationKind};
+use crate::{
+    client::ZeptoMailClient,
+    Template,
+    TemplateDefinition,
+    TemplateListOptions,
+    TemplatePage,
+    ZeptoMailError,
+};
+
+impl ZeptoMailClient {
+    /// Lists one page of the templates of a Mail Agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `mail_agent_alias` - The alias of the Mail Agent the templates belong to.
+    /// * `options` - A `TemplateListOptions` with the offset and size of the page.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(TemplatePage)` - The templates on the page, with the options for the next page.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the Mail Agent alias is empty, `.` or `..`.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, TemplateListOptions};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let mut options = Some(TemplateListOptions::new().limit(50));
+    ///     while let Some(page_options) = options {
+    ///         let page = client.list_templates("your_mail_agent_alias", page_options).await?;
+    ///         for template in &page.templates {
+    ///             println!("{}: {}", template.template_key, template.template_name);
+    ///         }
+    ///         options = page.next_page();
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_templates(
+        &self,
+        mail_agent_alias: &str,
+        options: TemplateListOptions
+    ) -> Result<TemplatePage, ZeptoMailError> {
+        let response: TemplateResponse = self.execute(&ListTemplates { mail_agent_alias, options }).await?;
+        Ok(TemplatePage {
+            templates: response.data,
+            offset: options.offset,
+            limit: options.limit,
+        })
+    }
+
+    /// Fetches a template of a Mail Agent by its key.
+    ///
+    /// # Arguments
+    ///
+    /// * `mail_agent_alias` - The alias of the Mail Agent the template belongs to.
+    /// * `template_key` - The key of the template.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(Template)` - The template, including its subject and HTML body.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the Mail Agent alias or template key is empty, `.` or `..`.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response, e.g. because the template does not exist.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
+    /// * `ZeptoMailError::UnexpectedResponse` - If the response does not contain a template.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let template = client.get_template("your_mail_agent_alias", "your_template_key").await?;
+    ///     println!("{} uses {:?}", template.template_name, template.merge_fields());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_template(
+        &self,
+        mail_agent_alias: &str,
+        template_key: &str
+    ) -> Result<Template, ZeptoMailError> {
+        let response: TemplateResponse = self.execute(&GetTemplate { mail_agent_alias, template_key }).await?;
+        single_template(response)
+    }
+
+    /// Creates a template in a Mail Agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `mail_agent_alias` - The alias of the Mail Agent to create the template in.
+    /// * `definition` - A `TemplateDefinition` with the name, subject, HTML body and optional alias of the template.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(Template)` - The created template, including the key to send it with.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the Mail Agent alias is empty, `.` or `..`, or the name, subject or HTML body is empty.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
+    /// * `ZeptoMailError::UnexpectedResponse` - If the response does not contain a template.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, TemplateDefinition};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let definition = TemplateDefinition::new(
+    ///         "Welcome",
+    ///         "Welcome, {{name}}",
+    ///         "<p>Hi {{name}}, welcome aboard.</p>",
+    ///     )
+    ///     .alias("welcome");
+    ///
+    ///     let template = client.create_template("your_mail_agent_alias", &definition).await?;
+    ///     println!("Created template {}", template.template_key);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_template(
+        &self,
+        mail_agent_alias: &str,
+        definition: &TemplateDefinition
+    ) -> Result<Template, ZeptoMailError> {
+        let response: TemplateResponse = self.execute(&CreateTemplate { mail_agent_alias, definition }).await?;
+        single_template(response)
+    }
+
+    /// Replaces the content of a template of a Mail Agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `mail_agent_alias` - The alias of the Mail Agent the template belongs to.
+    /// * `template_key` - The key of the template.
+    /// * `definition` - A `TemplateDefinition` with the new name, subject, HTML body and optional alias.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(Template)` - The updated template.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the Mail Agent alias or template key is empty, `.` or `..`, or the name, subject or HTML body is empty.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response, e.g. because the template does not exist.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
+    /// * `ZeptoMailError::UnexpectedResponse` - If the response does not contain a template.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region, TemplateDefinition};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     let template = client.get_template("your_mail_agent_alias", "your_template_key").await?;
+    ///     let mut definition = TemplateDefinition::from(&template);
+    ///     definition.subject = "Welcome aboard, {{name}}".to_string();
+    ///
+    ///     client.update_template("your_mail_agent_alias", &template.template_key, &definition).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update_template(
+        &self,
+        mail_agent_alias: &str,
+        template_key: &str,
+        definition: &TemplateDefinition
+    ) -> Result<Template, ZeptoMailError> {
+        let update: UpdateTemplate = UpdateTemplate { mail_agent_alias, template_key, definition };
+        let response: TemplateResponse = self.execute(&update).await?;
+        single_template(response)
+    }
+
+    /// Deletes a template of a Mail Agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `mail_agent_alias` - The alias of the Mail Agent the template belongs to.
+    /// * `template_key` - The key of the template.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result`:
+    /// * `Ok(())` - If the template is deleted.
+    /// * `Err(ZeptoMailError)` - If an error occurs, containing the error details.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the Mail Agent alias or template key is empty, `.` or `..`.
+    /// * `ZeptoMailError::ApiError` - If the API returns an error response, e.g. because the template does not exist.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
+    /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use zeptomail_rs::{ZeptoMailClient, Region};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
+    ///
+    ///     client.delete_template("your_mail_agent_alias", "your_template_key").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_template(
+        &self,
+        mail_agent_alias: &str,
+        template_key: &str
+    ) -> Result<(), ZeptoMailError> {
+        let _: IgnoredResponse = self.execute(&DeleteTemplate { mail_agent_alias, template_key }).await?;
+        Ok(())
+    }
+}
+
+fn single_template(response: TemplateResponse) -> Result<Template, ZeptoMailError> {
+    response
+        .data
+        .into_iter()
+        .next()
+        .ok_or_else(|| ZeptoMailError::UnexpectedResponse("The response does not contain a template".to_string()))
+}
+
+//The alias and key are path segments. An empty one, `.` or `..` would address a different endpoint.
+fn check_segments(segments: &[(&str, &str)]) -> ValidationErrors {
+    let mut errors: ValidationErrors = ValidationErrors::default();
+    for (field, segment) in segments {
+        if segment.trim().is_empty() {
+            errors.push(*field, ViolationKind::Missing);
+        } else if matches!(*segment, "." | "..") {
+            errors.push(*field, ViolationKind::InvalidPathSegment(segment.to_string()));
+        }
+    }
+    errors
+}
+
+fn check_definition(
+    errors: &mut ValidationErrors,
+    definition: &TemplateDefinition,
+    rules: &ValidationRules
+) {
+    if let Err(definition_errors) = definition.validate_with(rules) {
+        for violation in definition_errors.into_violations() {
+            errors.push(violation.field, violation.kind);
+        }
+    }
+}
+
+struct ListTemplates<'a> {
+    mail_agent_alias: &'a str,
+    options: TemplateListOptions,
+}
+
+impl Endpoint for ListTemplates<'_> {
+    type Response = TemplateResponse;
+
+    const METHOD: Method = Method::GET;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["mailagents", self.mail_agent_alias, "templates"]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("offset", self.options.offset.to_string()),
+            ("limit", self.options.limit.to_string()),
+        ]
+    }
+
+    fn validate_request(&self, _rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        check_segments(&[("mail_agent_alias", self.mail_agent_alias)]).into_result()
+    }
+}
+
+struct GetTemplate<'a> {
+    mail_agent_alias: &'a str,
+    template_key: &'a str,
+}
+
+impl Endpoint for GetTemplate<'_> {
+    type Response = TemplateResponse;
+
+    const METHOD: Method = Method::GET;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["mailagents", self.mail_agent_alias, "templates", self.template_key]
+    }
+
+    fn validate_request(&self, _rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        check_segments(&[("mail_agent_alias", self.mail_agent_alias), ("template_key", self.template_key)])
+            .into_result()
+    }
+}
+
+struct CreateTemplate<'a> {
+    mail_agent_alias: &'a str,
+    definition: &'a TemplateDefinition,
+}
+
+impl Endpoint for CreateTemplate<'_> {
+    type Response = TemplateResponse;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["mailagents", self.mail_agent_alias, "templates"]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(self.definition)
+    }
+
+    //Retrying a creation after a timeout could create the template twice.
+    fn is_replayable(&self) -> bool {
+        false
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = check_segments(&[("mail_agent_alias", self.mail_agent_alias)]);
+        check_definition(&mut errors, self.definition, rules);
+        errors.into_result()
+    }
+}
+
+struct UpdateTemplate<'a> {
+    mail_agent_alias: &'a str,
+    template_key: &'a str,
+    definition: &'a TemplateDefinition,
+}
+
+impl Endpoint for UpdateTemplate<'_> {
+    type Response = TemplateResponse;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["mailagents", self.mail_agent_alias, "templates", self.template_key]
+    }
+
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(self.definition)
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors =
+            check_segments(&[("mail_agent_alias", self.mail_agent_alias), ("template_key", self.template_key)]);
+        check_definition(&mut errors, self.definition, rules);
+        errors.into_result()
+    }
+}
+
+struct DeleteTemplate<'a> {
+    mail_agent_alias: &'a str,
+    template_key: &'a str,
+}
+
+impl Endpoint for DeleteTemplate<'_> {
+    type Response = IgnoredResponse;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["mailagents", self.mail_agent_alias, "templates", self.template_key]
+    }
+
+    fn validate_request(&self, _rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        check_segments(&[("mail_agent_alias", self.mail_agent_alias), ("template_key", self.template_key)])
+            .into_result()
+    }
+}
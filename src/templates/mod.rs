@@ -1,2 +1,3 @@
 pub mod batch;
+pub mod manage;
 pub mod single;
\ No newline at end of file
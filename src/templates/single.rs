@@ -1,14 +1,15 @@
 //! ## Send Email with Template
 //! This module contains the implementation for sending template emails using the ZeptoMail API.
 
-use reqwest::{Response, StatusCode};
+use reqwest::Method;
 
+use crate::request::{Endpoint, Payload};
+use crate::validation::{Validate, ValidationErrors, ValidationRules};
 use crate::{
     client::ZeptoMailClient,
     TemplateEmailRequest,
     ApiResponse,
     ZeptoMailError,
-    ApiError,
 };
 
 impl ZeptoMailClient {
@@ -31,33 +32,34 @@ impl ZeptoMailClient {
     /// # Errors
     ///
     /// This function can return the following errors:
+    /// * `ZeptoMailError::Validation` - If the request fails validation before it is sent.
+    /// * `ZeptoMailError::Suppressed` - If the suppression store rejects the send, see [`SuppressionPolicy`](crate::suppression::guard::SuppressionPolicy).
     /// * `ZeptoMailError::ApiError` - If the API returns an error response.
+    /// * `ZeptoMailError::HttpError` - If the request fails with a response that is not a ZeptoMail error, e.g. a gateway error page.
     /// * `ZeptoMailError::NetworkError` - If there is a network issue while sending the request.
-    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing or deserializing the request/response.
+    /// * `ZeptoMailError::SerializationError` - If there is an issue serializing the request.
+    /// * `ZeptoMailError::DeserializationError` - If the success response cannot be parsed.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use zeptomail_rs::{ZeptoMailClient, TemplateEmailRequest, ApiResponse, EmailAddress, Recipient};
+    /// use zeptomail_rs::{ZeptoMailClient, Region, TemplateEmailRequest, EmailAddress};
     /// use std::error::Error;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let client = ZeptoMailClient::new("your_api_key")?;
+    ///     let client = ZeptoMailClient::new("your_api_key", Region::Eu)?;
     ///
-    ///     let template_email_request = TemplateEmailRequest {
-    ///         template_key: "your_template_key".to_string(),
-    ///         bounce_address: Some("bounce@example.com".to_string()),
-    ///         sender: EmailAddress::new("sender@example.com".to_string()),
-    ///         recipients: vec![Recipient::new("recipient@example.com".to_string())],
-    ///         reply_to: Some(vec![EmailAddress::new("replyto@example.com".to_string())]),
-    ///         track_clicks: Some(true),
-    ///         track_opens: Some(true),
-    ///         client_reference: Some("client_ref".to_string()),
-    ///         mime_headers: None,
-    ///         attachments: None,
-    ///         merge_info: Some(std::collections::HashMap::new()),
-    ///     };
+    ///     let template_email_request = TemplateEmailRequest::builder("your_template_key")
+    ///         .bounce_address("bounce@example.com")
+    ///         .sender(EmailAddress::new("sender@example.com").with_name("Sender Name"))
+    ///         .to("recipient@example.com")
+    ///         .reply_to("replyto@example.com")
+    ///         .track_clicks(true)
+    ///         .track_opens(true)
+    ///         .client_reference("client_ref")
+    ///         .merge_field("name", "Recipient")
+    ///         .build()?;
     ///
     ///     match client.send_template_email(template_email_request).await {
     ///         Ok(response) => println!("Email sent successfully: {:?}", response),
@@ -71,28 +73,30 @@ impl ZeptoMailClient {
         &self,
         template_email_request: TemplateEmailRequest
     ) -> Result<ApiResponse, ZeptoMailError> {
-        let endpoint: &str = "email/template";
-        let url: String = format!("{}/{}", self.base_url, endpoint);
+        self.send_unsuppressed(template_email_request).await
+    }
+}
+
+impl Endpoint for TemplateEmailRequest {
+    type Response = ApiResponse;
 
-        let response: Response = self.client
-            .post(&url)
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Zoho-enczapikey {}", self.api_key))
-            .json(&template_email_request)
-            .send()
-            .await?;
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> Vec<&str> {
+        vec!["email", "template"]
+    }
 
-        let status: StatusCode = response.status();
+    fn payload(&self) -> Result<Payload, ZeptoMailError> {
+        Payload::json(self)
+    }
+
+    fn validate_request(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        self.validate_with(rules)
+    }
 
-        if status.is_success() {
-            let response_text: String = response.text().await?;
-            let success_response: ApiResponse = serde_json::from_str(&response_text)?;
-            Ok(success_response)
-        } else {
-            let response_text: String = response.text().await?;
-            let api_error: ApiError = serde_json::from_str(&response_text)?;
-            Err(ZeptoMailError::ApiError(api_error))
-        }
+    fn recipient_count(&self) -> u32 {
+        let copies: usize = self.carbon_copy.as_ref().map_or(0, Vec::len)
+            + self.blind_carbon_copy.as_ref().map_or(0, Vec::len);
+        (self.recipients.len() + copies) as u32
     }
 }
\ No newline at end of file
@@ -0,0 +1,148 @@
+//! ## Memory Transport
+//! An in-memory [`Transport`] for tests, which answers requests with queued responses.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+
+use crate::transport::{HttpRequest, HttpResponse, RequestBody, Transport};
+use crate::ZeptoMailError;
+
+/// A request received by a [`MemoryTransport`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl RecordedRequest {
+    /// Parses the body as JSON.
+    pub fn json(&self) -> Result<serde_json::Value, ZeptoMailError> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+#[derive(Default)]
+struct MemoryState {
+    responses: VecDeque<Result<HttpResponse, ZeptoMailError>>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// A [`Transport`] that never touches the network.
+///
+/// Responses are queued up front and returned in order, one per request. Every request is recorded
+/// so tests can assert on what the client sent. Clones share the same queue and recordings.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{ZeptoMailClient, Region, FileUploadRequest};
+/// use zeptomail_rs::transport::MemoryTransport;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let transport = MemoryTransport::new();
+///     transport.push_json(201, serde_json::json!({
+///         "file_cache_key": "cache-key",
+///         "data": [],
+///         "message": "OK",
+///         "object": "files",
+///     }));
+///
+///     let client = ZeptoMailClient::builder("your_api_key", Region::Eu)
+///         .transport(transport.clone())
+///         .build()
+///         .unwrap();
+///
+///     let file_upload_request = FileUploadRequest::new("example.txt", "text/plain", "Hello, world!");
+///     let response = client.upload_file_to_cache(file_upload_request).await.unwrap();
+///
+///     assert_eq!(response.file_cache_key, "cache-key");
+///     assert_eq!(transport.requests()[0].url, "https://api.zeptomail.eu/v1.1/files?name=example.txt");
+///     assert_eq!(transport.requests()[0].body, "Hello, world!");
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryTransport {
+    state: Arc<Mutex<MemoryState>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a response.
+    pub fn push_response(&self, response: HttpResponse) {
+        self.state().responses.push_back(Ok(response));
+    }
+
+    /// Queues a response with the given status code and JSON body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` is not a valid HTTP status code.
+    pub fn push_json(&self, status: u16, body: serde_json::Value) {
+        let status: StatusCode = StatusCode::from_u16(status).expect("invalid status code");
+        let mut response: HttpResponse = HttpResponse::new(status, body.to_string());
+        response.headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            "application/json".parse().expect("valid header value"),
+        );
+        self.push_response(response);
+    }
+
+    /// Queues an error, as if the request never got a response.
+    pub fn push_error(&self, error: ZeptoMailError) {
+        self.state().responses.push_back(Err(error));
+    }
+
+    /// All requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state().requests.clone()
+    }
+
+    /// The number of queued responses that have not been used yet.
+    pub fn pending(&self) -> usize {
+        self.state().responses.len()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, ZeptoMailError> {
+        let body: Bytes = match request.body {
+            RequestBody::Empty => Bytes::new(),
+            RequestBody::Bytes(bytes) => bytes,
+            RequestBody::Stream { stream, .. } => {
+                let chunks: Vec<Bytes> = stream.try_collect().await.map_err(ZeptoMailError::IoError)?;
+                chunks.concat().into()
+            }
+        };
+
+        let mut state = self.state();
+        state.requests.push(RecordedRequest {
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+            body,
+        });
+
+        state.responses.pop_front().unwrap_or_else(|| {
+            Err(ZeptoMailError::UnexpectedResponse(
+                "MemoryTransport has no queued response".to_string(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,99 @@
+//! ## Transport
+//! This module contains the HTTP transport abstraction the client sends its requests through.
+//!
+//! [`ZeptoMailClient`](crate::ZeptoMailClient) never talks to the network directly. It describes each
+//! request as an [`HttpRequest`] and hands it to a [`Transport`], which returns the [`HttpResponse`].
+//! The default transport is [`ReqwestTransport`]; [`MemoryTransport`] answers with queued responses
+//! so applications can be tested without the network.
+
+pub mod memory;
+pub mod reqwest_transport;
+
+use std::fmt;
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+
+use crate::ZeptoMailError;
+
+pub use memory::{MemoryTransport, RecordedRequest};
+pub use reqwest_transport::ReqwestTransport;
+
+/// A description of an HTTP request to the ZeptoMail API.
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: RequestBody,
+    //Transports should stop reading the response body once it exceeds this many bytes.
+    pub max_response_bytes: Option<usize>,
+}
+
+/// A request body that is produced chunk by chunk, e.g. while reading a file.
+pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync>>;
+
+/// The body of an [`HttpRequest`].
+pub enum RequestBody {
+    Empty,
+    Bytes(Bytes),
+    //A body of `length` bytes that is streamed instead of held in memory. The client also sets it
+    //as the Content-Length header.
+    Stream { stream: BodyStream, length: u64 },
+}
+
+impl RequestBody {
+    /// The length of the body in bytes.
+    pub fn len(&self) -> u64 {
+        match self {
+            RequestBody::Empty => 0,
+            RequestBody::Bytes(bytes) => bytes.len() as u64,
+            RequestBody::Stream { length, .. } => *length,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for RequestBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestBody::Empty => f.write_str("Empty"),
+            RequestBody::Bytes(bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            RequestBody::Stream { length, .. } => f.debug_struct("Stream").field("length", length).finish_non_exhaustive(),
+        }
+    }
+}
+
+/// The status, headers and body returned for an [`HttpRequest`].
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    pub fn new(status: StatusCode, body: impl Into<Bytes>) -> Self {
+        HttpResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends [`HttpRequest`]s on behalf of a [`ZeptoMailClient`](crate::ZeptoMailClient).
+///
+/// Implementations only move bytes: status codes are returned as-is and interpreted by the client.
+/// An `Err` should only be returned when no response was received at all.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, ZeptoMailError>;
+}
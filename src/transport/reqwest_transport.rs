@@ -0,0 +1,66 @@
+//! ## Reqwest Transport
+//! The default [`Transport`], backed by a `reqwest::Client`.
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use reqwest::header::HeaderMap;
+use reqwest::{Body, Client, Response, StatusCode};
+
+use crate::transport::{HttpRequest, HttpResponse, RequestBody, Transport};
+use crate::ZeptoMailError;
+
+/// A [`Transport`] that sends requests over the network with `reqwest`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    /// The underlying `reqwest::Client`.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, ZeptoMailError> {
+        let mut builder = self.client
+            .request(request.method, &request.url)
+            .headers(request.headers);
+
+        match request.body {
+            RequestBody::Empty => {}
+            RequestBody::Bytes(bytes) => builder = builder.body(bytes),
+            RequestBody::Stream { stream, .. } => builder = builder.body(Body::wrap_stream(stream)),
+        }
+
+        let mut response: Response = builder.send().await?;
+        let status: StatusCode = response.status();
+        let headers: HeaderMap = response.headers().clone();
+
+        let body: Bytes = match request.max_response_bytes {
+            None => response.bytes().await?,
+            Some(limit) => {
+                if response.content_length().is_some_and(|length| length > limit as u64) {
+                    return Err(ZeptoMailError::ResponseTooLarge { limit });
+                }
+
+                let mut body: BytesMut = BytesMut::new();
+                while let Some(chunk) = response.chunk().await? {
+                    if body.len() + chunk.len() > limit {
+                        return Err(ZeptoMailError::ResponseTooLarge { limit });
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                body.freeze()
+            }
+        };
+
+        Ok(HttpResponse { status, headers, body })
+    }
+}
@@ -0,0 +1,588 @@
+//! ## Validation
+//! This module contains the checks requests go through before they are sent.
+//!
+//! Requests that ZeptoMail would reject, such as an email without a body or an attachment with both
+//! `content` and `file_cache_key`, are caught locally instead of after a network round-trip. The
+//! client validates every request by default; this can be turned off with
+//! [`ZeptoMailClientBuilder::validate_requests`](crate::ZeptoMailClientBuilder::validate_requests).
+
+use std::fmt;
+
+use crate::{
+    Attachment,
+    BatchEmailRequest,
+    BlockedExtensions,
+    BatchTemplateEmailRequest,
+    EmailAddress,
+    EmailRequest,
+    FileUploadRequest,
+    InlineImage,
+    MAX_MESSAGE_SIZE,
+    Recipient,
+    Suppression,
+    SuppressionType,
+    TemplateDefinition,
+    TemplateEmailRequest,
+};
+
+/// What is wrong with a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ViolationKind {
+    /// A required field is missing or empty.
+    Missing,
+    /// The field is not a valid email address.
+    InvalidEmailAddress(String),
+    /// Neither `htmlbody` nor `textbody` is set.
+    MissingBody,
+    /// An attachment has both `content` and `file_cache_key`.
+    ConflictingAttachmentSource,
+    /// An attachment has neither `content` nor `file_cache_key`.
+    MissingAttachmentSource,
+    /// An attachment's name has an extension ZeptoMail rejects.
+    BlockedExtension { name: String, extension: String },
+    /// The field is not a valid MIME type.
+    InvalidContentType(String),
+    /// A domain is not a valid domain name.
+    InvalidDomain(String),
+    /// A value used as a URL path segment is `.` or `..`, which would address a different endpoint.
+    InvalidPathSegment(String),
+    /// The body, attachments and inline images add up to more than the message size limit.
+    MessageTooLarge { size: usize, limit: usize },
+}
+
+/// A single problem with a request, located by the path of the offending field,
+/// e.g. `recipients[1].email_address.address`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub field: String,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ViolationKind::Missing => write!(f, "`{}` is required", self.field),
+            ViolationKind::InvalidEmailAddress(address) => {
+                write!(f, "`{}` is not a valid email address: {:?}", self.field, address)
+            }
+            ViolationKind::MissingBody => write!(f, "`{}`: either `htmlbody` or `textbody` is required", self.field),
+            ViolationKind::ConflictingAttachmentSource => {
+                write!(f, "`{}` must not have both `content` and `file_cache_key`", self.field)
+            }
+            ViolationKind::MissingAttachmentSource => {
+                write!(f, "`{}` needs either `content` or `file_cache_key`", self.field)
+            }
+            ViolationKind::BlockedExtension { name, extension } => {
+                write!(f, "`{}`: {:?} has the blocked extension `.{}`", self.field, name, extension)
+            }
+            ViolationKind::InvalidContentType(content_type) => {
+                write!(f, "`{}` is not a valid MIME type: {:?}", self.field, content_type)
+            }
+            ViolationKind::InvalidDomain(domain) => {
+                write!(f, "`{}` is not a valid domain: {:?}", self.field, domain)
+            }
+            ViolationKind::InvalidPathSegment(segment) => {
+                write!(f, "`{}` is not a valid path segment: {:?}", self.field, segment)
+            }
+            ViolationKind::MessageTooLarge { size, limit } => {
+                write!(f, "`{}`: about {} bytes exceed the limit of {} bytes", self.field, size, limit)
+            }
+        }
+    }
+}
+
+/// All violations found in a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    violations: Vec<Violation>,
+}
+
+impl ValidationErrors {
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    pub fn into_violations(self) -> Vec<Violation> {
+        self.violations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Whether any violation is about `field`.
+    pub fn contains(&self, field: &str) -> bool {
+        self.violations.iter().any(|violation| violation.field == field)
+    }
+
+    pub(crate) fn single(field: impl Into<String>, kind: ViolationKind) -> Self {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+        errors.push(field, kind);
+        errors
+    }
+
+    pub(crate) fn push(&mut self, field: impl Into<String>, kind: ViolationKind) {
+        self.violations.push(Violation { field: field.into(), kind });
+    }
+
+    pub(crate) fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, violation) in self.violations.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Settings for the checks in [`Validate`].
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{Attachment, BlockedExtensions, EmailRequest, Region, ZeptoMailClient};
+/// use zeptomail_rs::transport::MemoryTransport;
+/// use zeptomail_rs::validation::ValidationRules;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let transport = MemoryTransport::new();
+///     transport.push_json(200, serde_json::json!({ "data": [], "message": "OK", "object": "email", "request_id": "2d6f.1e0b" }));
+///
+///     let rules = ValidationRules::new()
+///         .blocked_extensions(BlockedExtensions::default().remove("tmp"));
+///
+///     let client = ZeptoMailClient::builder("your_api_key", Region::Eu)
+///         .transport(transport)
+///         .validation_rules(rules)
+///         .build()
+///         .unwrap();
+///
+///     //Blocked by the default rules, but allowed by the client's.
+///     let email_request = EmailRequest::builder()
+///         .sender("sender@example.com")
+///         .to("recipient@example.com")
+///         .subject("Nightly backup")
+///         .text("The backup is attached.")
+///         .attach(Attachment::from_bytes("backup.tmp", b"backup").unwrap())
+///         .build()
+///         .unwrap();
+///
+///     assert!(client.send_email(email_request).await.is_ok());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationRules {
+    pub(crate) blocked_extensions: BlockedExtensions,
+    pub(crate) max_message_size: usize,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        ValidationRules {
+            blocked_extensions: BlockedExtensions::default(),
+            max_message_size: MAX_MESSAGE_SIZE,
+        }
+    }
+}
+
+impl ValidationRules {
+    /// The rules ZeptoMail currently enforces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the attachment extensions to reject. Defaults to [`BlockedExtensions::default`].
+    pub fn blocked_extensions(mut self, blocked_extensions: BlockedExtensions) -> Self {
+        self.blocked_extensions = blocked_extensions;
+        self
+    }
+
+    /// Sets the largest message, in bytes of decoded content, to accept. Defaults to [`MAX_MESSAGE_SIZE`].
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+}
+
+/// Checks a request for problems ZeptoMail would reject it for.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::{Attachment, EmailRequest};
+/// use zeptomail_rs::validation::{Validate, ViolationKind};
+///
+/// let email_request = EmailRequest::builder()
+///     .sender("sender@example.com")
+///     .to("not-an-address")
+///     .subject("Test Email")
+///     .attach(Attachment::new("payload.exe", "application/octet-stream", "TVqQAAMAAAAEAAAA"))
+///     .build()
+///     .unwrap();
+///
+/// let errors = email_request.validate().unwrap_err();
+/// assert!(errors.contains("recipients[0].email_address.address"));
+/// assert!(errors.contains("attachments[0].name"));
+/// assert!(errors.violations().iter().any(|violation| violation.kind == ViolationKind::MissingBody));
+/// ```
+pub trait Validate {
+    /// Checks the request against the [default rules](ValidationRules::default).
+    ///
+    /// A client with custom rules checks requests against those instead; use
+    /// [`validate_with`](Self::validate_with) to check a request against them before sending it.
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.validate_with(&ValidationRules::default())
+    }
+
+    /// Checks the request against custom rules.
+    fn validate_with(&self, rules: &ValidationRules) -> Result<(), ValidationErrors>;
+}
+
+impl Validate for EmailRequest {
+    fn validate_with(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        check_optional_address(&mut errors, "bounce_address", self.bounce_address.as_deref());
+        check_address(&mut errors, "sender", &self.sender);
+        check_recipients(&mut errors, "recipients", &self.recipients, true);
+        check_addresses(&mut errors, "reply_to", self.reply_to.as_deref());
+        check_subject(&mut errors, &self.subject);
+        check_body(&mut errors, &self.htmlbody, &self.textbody);
+        check_recipients(&mut errors, "carbon_copy", self.carbon_copy.as_deref().unwrap_or_default(), false);
+        check_recipients(&mut errors, "blind_carbon_copy", self.blind_carbon_copy.as_deref().unwrap_or_default(), false);
+        check_attachments(&mut errors, self.attachments.as_deref(), &rules.blocked_extensions);
+        check_inline_images(&mut errors, self.inline_images.as_deref());
+        check_message_size(
+            &mut errors,
+            &[&self.subject, self.htmlbody.as_deref().unwrap_or_default(), self.textbody.as_deref().unwrap_or_default()],
+            self.attachments.as_deref(),
+            self.inline_images.as_deref(),
+            rules.max_message_size,
+        );
+
+        errors.into_result()
+    }
+}
+
+impl Validate for BatchEmailRequest {
+    fn validate_with(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        check_address(&mut errors, "sender", &self.sender);
+        check_recipients(&mut errors, "recipients", &self.recipients, true);
+        check_subject(&mut errors, &self.subject);
+        check_body(&mut errors, &self.htmlbody, &self.textbody);
+        check_recipients(&mut errors, "carbon_copy", self.carbon_copy.as_deref().unwrap_or_default(), false);
+        check_recipients(&mut errors, "blind_carbon_copy", self.blind_carbon_copy.as_deref().unwrap_or_default(), false);
+        check_attachments(&mut errors, self.attachments.as_deref(), &rules.blocked_extensions);
+        check_inline_images(&mut errors, self.inline_images.as_deref());
+        check_message_size(
+            &mut errors,
+            &[&self.subject, self.htmlbody.as_deref().unwrap_or_default(), self.textbody.as_deref().unwrap_or_default()],
+            self.attachments.as_deref(),
+            self.inline_images.as_deref(),
+            rules.max_message_size,
+        );
+
+        errors.into_result()
+    }
+}
+
+impl Validate for TemplateEmailRequest {
+    fn validate_with(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        check_template_key(&mut errors, &self.template_key);
+        check_optional_address(&mut errors, "bounce_address", self.bounce_address.as_deref());
+        check_address(&mut errors, "sender", &self.sender);
+        check_recipients(&mut errors, "recipients", &self.recipients, true);
+        check_addresses(&mut errors, "reply_to", self.reply_to.as_deref());
+        check_recipients(&mut errors, "carbon_copy", self.carbon_copy.as_deref().unwrap_or_default(), false);
+        check_recipients(&mut errors, "blind_carbon_copy", self.blind_carbon_copy.as_deref().unwrap_or_default(), false);
+        check_attachments(&mut errors, self.attachments.as_deref(), &rules.blocked_extensions);
+        check_inline_images(&mut errors, self.inline_images.as_deref());
+        check_message_size(
+            &mut errors,
+            &[],
+            self.attachments.as_deref(),
+            self.inline_images.as_deref(),
+            rules.max_message_size,
+        );
+
+        errors.into_result()
+    }
+}
+
+impl Validate for BatchTemplateEmailRequest {
+    fn validate_with(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        check_template_key(&mut errors, &self.template_key);
+        check_optional_address(&mut errors, "bounce_address", self.bounce_address.as_deref());
+        check_address(&mut errors, "sender", &self.sender);
+        check_recipients(&mut errors, "recipients", &self.recipients, true);
+        check_addresses(&mut errors, "reply_to", self.reply_to.as_deref());
+        check_recipients(&mut errors, "carbon_copy", self.carbon_copy.as_deref().unwrap_or_default(), false);
+        check_recipients(&mut errors, "blind_carbon_copy", self.blind_carbon_copy.as_deref().unwrap_or_default(), false);
+        check_attachments(&mut errors, self.attachments.as_deref(), &rules.blocked_extensions);
+        check_inline_images(&mut errors, self.inline_images.as_deref());
+        check_message_size(
+            &mut errors,
+            &[],
+            self.attachments.as_deref(),
+            self.inline_images.as_deref(),
+            rules.max_message_size,
+        );
+
+        errors.into_result()
+    }
+}
+
+impl Validate for TemplateDefinition {
+    fn validate_with(&self, _rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        if self.template_name.trim().is_empty() {
+            errors.push("template_name", ViolationKind::Missing);
+        }
+        check_subject(&mut errors, &self.subject);
+        if self.htmlbody.trim().is_empty() {
+            errors.push("htmlbody", ViolationKind::Missing);
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for Suppression {
+    fn validate_with(&self, _rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        match self.kind {
+            SuppressionType::Email => check_email(&mut errors, "value".to_string(), &self.value),
+            SuppressionType::Domain if self.value.trim().is_empty() => errors.push("value", ViolationKind::Missing),
+            SuppressionType::Domain if !is_valid_domain(&self.value) => {
+                errors.push("value", ViolationKind::InvalidDomain(self.value.clone()));
+            }
+            SuppressionType::Domain => {}
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for FileUploadRequest {
+    fn validate_with(&self, rules: &ValidationRules) -> Result<(), ValidationErrors> {
+        let mut errors: ValidationErrors = ValidationErrors::default();
+
+        if self.name.trim().is_empty() {
+            errors.push("name", ViolationKind::Missing);
+        } else if let Some(extension) = rules.blocked_extensions.find(&self.name) {
+            errors.push(
+                "name",
+                ViolationKind::BlockedExtension {
+                    name: self.name.clone(),
+                    extension: extension.to_string(),
+                },
+            );
+        }
+        if !is_valid_content_type(&self.content_type) {
+            errors.push("content_type", ViolationKind::InvalidContentType(self.content_type.clone()));
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Whether `address` looks like a deliverable email address: a local part and a dotted domain,
+/// separated by a single `@`, without whitespace.
+pub fn is_valid_email_address(address: &str) -> bool {
+    let Some((local, domain)) = address.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty() && !local.chars().any(char::is_whitespace) && is_valid_domain(domain)
+}
+
+/// Whether `domain` looks like a domain name, e.g. `example.com`: dotted labels without whitespace.
+pub fn is_valid_domain(domain: &str) -> bool {
+    !domain.contains('@')
+        && !domain.chars().any(char::is_whitespace)
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains("..")
+}
+
+//A `type/subtype` pair, optionally followed by parameters, that can be sent as a header.
+pub(crate) fn is_valid_content_type(content_type: &str) -> bool {
+    let essence: &str = content_type.split(';').next().unwrap_or_default().trim();
+    let Some((kind, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+
+    !kind.is_empty()
+        && !subtype.is_empty()
+        && !subtype.contains('/')
+        && content_type.bytes().all(|byte| byte.is_ascii_graphic() || byte == b' ')
+}
+
+fn check_email(errors: &mut ValidationErrors, field: String, address: &str) {
+    if address.trim().is_empty() {
+        errors.push(field, ViolationKind::Missing);
+    } else if !is_valid_email_address(address) {
+        errors.push(field, ViolationKind::InvalidEmailAddress(address.to_string()));
+    }
+}
+
+fn check_optional_address(errors: &mut ValidationErrors, field: &str, address: Option<&str>) {
+    if let Some(address) = address {
+        check_email(errors, field.to_string(), address);
+    }
+}
+
+fn check_address(errors: &mut ValidationErrors, field: &str, address: &EmailAddress) {
+    check_email(errors, format!("{}.address", field), &address.address);
+}
+
+fn check_addresses(errors: &mut ValidationErrors, field: &str, addresses: Option<&[EmailAddress]>) {
+    for (index, address) in addresses.unwrap_or_default().iter().enumerate() {
+        check_address(errors, &format!("{}[{}]", field, index), address);
+    }
+}
+
+fn check_recipients(errors: &mut ValidationErrors, field: &str, recipients: &[Recipient], required: bool) {
+    if required && recipients.is_empty() {
+        errors.push(field, ViolationKind::Missing);
+    }
+    for (index, recipient) in recipients.iter().enumerate() {
+        check_address(errors, &format!("{}[{}].email_address", field, index), &recipient.email_address);
+    }
+}
+
+fn check_subject(errors: &mut ValidationErrors, subject: &str) {
+    if subject.trim().is_empty() {
+        errors.push("subject", ViolationKind::Missing);
+    }
+}
+
+fn check_body(errors: &mut ValidationErrors, htmlbody: &Option<String>, textbody: &Option<String>) {
+    let is_blank = |body: &Option<String>| body.as_deref().is_none_or(|body| body.trim().is_empty());
+    if is_blank(htmlbody) && is_blank(textbody) {
+        errors.push("htmlbody", ViolationKind::MissingBody);
+    }
+}
+
+/// The checks the request builders run: the fields without which there is no request to send.
+/// Everything else is checked by the client against its own rules when the request is sent.
+pub(crate) fn check_required_fields(
+    template_key: Option<&str>,
+    sender: Option<&EmailAddress>,
+    recipients: &[Recipient]
+) -> Result<(), ValidationErrors> {
+    let mut errors: ValidationErrors = ValidationErrors::default();
+
+    if let Some(template_key) = template_key {
+        check_template_key(&mut errors, template_key);
+    }
+    if sender.is_none_or(|sender| sender.address.trim().is_empty()) {
+        errors.push("sender", ViolationKind::Missing);
+    }
+    if recipients.is_empty() {
+        errors.push("recipients", ViolationKind::Missing);
+    }
+
+    errors.into_result()
+}
+
+fn check_template_key(errors: &mut ValidationErrors, template_key: &str) {
+    if template_key.trim().is_empty() {
+        errors.push("template_key", ViolationKind::Missing);
+    }
+}
+
+fn check_attachments(
+    errors: &mut ValidationErrors,
+    attachments: Option<&[Attachment]>,
+    blocked_extensions: &BlockedExtensions
+) {
+    for (index, attachment) in attachments.unwrap_or_default().iter().enumerate() {
+        let field: String = format!("attachments[{}]", index);
+
+        if attachment.name.trim().is_empty() {
+            errors.push(format!("{}.name", field), ViolationKind::Missing);
+        } else if let Some(extension) = blocked_extensions.find(&attachment.name) {
+            errors.push(
+                format!("{}.name", field),
+                ViolationKind::BlockedExtension {
+                    name: attachment.name.clone(),
+                    extension: extension.to_string(),
+                },
+            );
+        }
+        match (&attachment.content, &attachment.file_cache_key) {
+            (Some(_), Some(_)) => errors.push(field, ViolationKind::ConflictingAttachmentSource),
+            (None, None) => errors.push(field, ViolationKind::MissingAttachmentSource),
+            _ => {}
+        }
+    }
+}
+
+fn check_inline_images(errors: &mut ValidationErrors, inline_images: Option<&[InlineImage]>) {
+    for (index, inline_image) in inline_images.unwrap_or_default().iter().enumerate() {
+        let field: String = format!("inline_images[{}]", index);
+
+        if inline_image.content_id.trim().is_empty() {
+            errors.push(format!("{}.content_id", field), ViolationKind::Missing);
+        }
+        if inline_image.mime_type.trim().is_empty() {
+            errors.push(format!("{}.mime_type", field), ViolationKind::Missing);
+        }
+        if inline_image.content.is_empty() {
+            errors.push(format!("{}.content", field), ViolationKind::Missing);
+        }
+    }
+}
+
+fn check_message_size(
+    errors: &mut ValidationErrors,
+    text: &[&str],
+    attachments: Option<&[Attachment]>,
+    inline_images: Option<&[InlineImage]>,
+    limit: usize
+) {
+    //Base64 encodes every 3 bytes as 4 characters.
+    let decoded_len = |content: &str| content.len() / 4 * 3;
+
+    let size: usize = text.iter().map(|part| part.len()).sum::<usize>()
+        + attachments
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|attachment| attachment.content.as_deref())
+            .map(decoded_len)
+            .sum::<usize>()
+        + inline_images
+            .unwrap_or_default()
+            .iter()
+            .map(|inline_image| decoded_len(&inline_image.content))
+            .sum::<usize>();
+
+    if size > limit {
+        errors.push("message", ViolationKind::MessageTooLarge { size, limit });
+    }
+}
@@ -0,0 +1,74 @@
+//! ## actix-web Webhook Route
+//! This module contains the [actix-web](https://docs.rs/actix-web) resource for a [`WebhookReceiver`].
+//! It is available with the `actix-web` feature.
+
+use std::sync::Arc;
+
+use actix_web::http::StatusCode as ActixStatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, Resource};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+
+use crate::webhooks::{WebhookHandler, WebhookReceiver};
+
+//The largest webhook body the resource accepts. actix-web's default of 256 KiB is too small for
+//batched payloads.
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+impl<H: WebhookHandler> WebhookReceiver<H> {
+    /// A resource that receives webhook calls as `POST` requests to `path`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use actix_web::{App, HttpServer};
+    /// use async_trait::async_trait;
+    /// use zeptomail_rs::webhooks::{WebhookHandler, WebhookReceiver, WebhookVerifier};
+    ///
+    /// struct Handler;
+    ///
+    /// #[async_trait]
+    /// impl WebhookHandler for Handler {}
+    ///
+    /// # async fn run() -> std::io::Result<()> {
+    /// HttpServer::new(|| {
+    ///     App::new().service(
+    ///         WebhookReceiver::new(Handler, WebhookVerifier::new("your_webhook_secret"))
+    ///             .resource("/webhooks/zeptomail"),
+    ///     )
+    /// })
+    /// .bind(("127.0.0.1", 8080))?
+    /// .run()
+    /// .await
+    /// # }
+    /// ```
+    pub fn resource(self, path: &str) -> Resource {
+        let receiver: Arc<WebhookReceiver<H>> = Arc::new(self);
+
+        web::resource(path)
+            .app_data(web::PayloadConfig::new(MAX_BODY_SIZE))
+            .route(web::post().to(move |request: HttpRequest, body: web::Bytes| {
+                let receiver: Arc<WebhookReceiver<H>> = receiver.clone();
+                async move {
+                    let status: StatusCode = receiver.receive(&headers(&request), &body).await;
+                    //Never acknowledge a call with a status that could not be carried over.
+                    let status: ActixStatusCode = ActixStatusCode::from_u16(status.as_u16())
+                        .unwrap_or(ActixStatusCode::INTERNAL_SERVER_ERROR);
+                    HttpResponse::new(status)
+                }
+            }))
+    }
+}
+
+//actix-web uses its own header types, so the headers are copied into the ones the receiver reads.
+fn headers(request: &HttpRequest) -> HeaderMap {
+    request
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            let name: HeaderName = HeaderName::from_bytes(name.as_str().as_bytes()).ok()?;
+            let value: HeaderValue = HeaderValue::from_bytes(value.as_bytes()).ok()?;
+            Some((name, value))
+        })
+        .collect()
+}
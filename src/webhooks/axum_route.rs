@@ -0,0 +1,48 @@
+//! ## axum Webhook Route
+//! This module contains the [axum](https://docs.rs/axum) route for a [`WebhookReceiver`]. It is
+//! available with the `axum` feature.
+
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::http::HeaderMap;
+use axum::routing::post;
+use axum::Router;
+
+use crate::webhooks::{WebhookHandler, WebhookReceiver};
+
+impl<H: WebhookHandler> WebhookReceiver<H> {
+    /// A router that receives webhook calls as `POST` requests to `path`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use async_trait::async_trait;
+    /// use zeptomail_rs::WebhookEvent;
+    /// use zeptomail_rs::models::webhook::Bounce;
+    /// use zeptomail_rs::webhooks::{HandlerError, WebhookHandler, WebhookReceiver, WebhookVerifier};
+    ///
+    /// struct Handler;
+    ///
+    /// #[async_trait]
+    /// impl WebhookHandler for Handler {
+    ///     async fn on_hard_bounce(&self, event: &WebhookEvent, _bounce: &Bounce) -> Result<(), HandlerError> {
+    ///         println!("{:?} bounced", event.recipient());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let app: axum::Router = axum::Router::new().merge(
+    ///     WebhookReceiver::new(Handler, WebhookVerifier::new("your_webhook_secret"))
+    ///         .router("/webhooks/zeptomail"),
+    /// );
+    /// ```
+    pub fn router<S: Clone + Send + Sync + 'static>(self, path: &str) -> Router<S> {
+        let receiver: Arc<WebhookReceiver<H>> = Arc::new(self);
+
+        Router::new().route(
+            path,
+            post(move |headers: HeaderMap, body: Bytes| async move { receiver.receive(&headers, &body).await }),
+        )
+    }
+}
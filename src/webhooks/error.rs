@@ -0,0 +1,68 @@
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// An error handling a webhook call.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WebhookError {
+    /// The body is not JSON, or an event does not have the expected shape.
+    MalformedPayload(serde_json::Error),
+    /// The body is JSON but not a ZeptoMail webhook payload.
+    UnexpectedPayload(String),
+    /// The call has no signature or token.
+    MissingSignature,
+    /// The signature header cannot be parsed.
+    MalformedSignature(String),
+    /// The signature or token does not match.
+    InvalidSignature,
+    /// The call was signed too long before or after the current time, e.g. because it is replayed.
+    StaleTimestamp {
+        timestamp: SystemTime,
+        tolerance: Duration,
+    },
+}
+
+impl WebhookError {
+    /// Whether the call could not be authenticated, which a webhook handler should answer with
+    /// `401 Unauthorized`. Other errors are about the body and map to `400 Bad Request`.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(
+            self,
+            WebhookError::MissingSignature
+                | WebhookError::MalformedSignature(_)
+                | WebhookError::InvalidSignature
+                | WebhookError::StaleTimestamp { .. }
+        )
+    }
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::MalformedPayload(e) => write!(f, "Malformed Payload: {}", e),
+            WebhookError::UnexpectedPayload(msg) => write!(f, "Unexpected Payload: {}", msg),
+            WebhookError::MissingSignature => write!(f, "Missing Signature: the call is not signed"),
+            WebhookError::MalformedSignature(msg) => write!(f, "Malformed Signature: {}", msg),
+            WebhookError::InvalidSignature => write!(f, "Invalid Signature: the signature does not match"),
+            WebhookError::StaleTimestamp { tolerance, .. } => {
+                write!(f, "Stale Timestamp: the call was signed more than {:?} from now", tolerance)
+            }
+        }
+    }
+}
+
+impl Error for WebhookError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WebhookError::MalformedPayload(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for WebhookError {
+    fn from(error: serde_json::Error) -> Self {
+        WebhookError::MalformedPayload(error)
+    }
+}
@@ -0,0 +1,23 @@
+//! ## Webhooks
+//! This module contains the parser for the notifications ZeptoMail POSTs to webhooks about sent
+//! emails: bounces, opens, clicks, spam complaints and deliveries, the checks that a call was
+//! sent by ZeptoMail, and a receiver that dispatches the events to application code.
+//!
+//! Events carry the `request_id` and `client_reference` of the send they are about, so they can be
+//! linked back to the [`ApiResponse`](crate::ApiResponse) of the original request.
+//!
+//! With the `axum` or `actix-web` feature, [`WebhookReceiver`] also provides a ready-made route.
+
+pub mod error;
+pub mod parse;
+pub mod receiver;
+pub mod signature;
+#[cfg(feature = "actix-web")]
+pub mod actix_route;
+#[cfg(feature = "axum")]
+pub mod axum_route;
+
+pub use error::WebhookError;
+pub use parse::parse_webhook;
+pub use receiver::{HandlerError, WebhookHandler, WebhookReceiver};
+pub use signature::{verify_token, WebhookVerifier, SIGNATURE_HEADER};
@@ -0,0 +1,190 @@
+//! ## Webhook Parsing
+//! This module contains the parser for webhook payloads.
+//!
+//! A payload lists the names of its events in `event_name` and one entry per email in `event_message`,
+//! each with the details of the event for every affected recipient. Several payloads may also be
+//! delivered together as a JSON array.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::webhook::{EmailInfo, EventKind, WebhookEvent};
+use crate::webhooks::WebhookError;
+
+#[derive(Debug, Deserialize)]
+struct Payload {
+    #[serde(default)]
+    event_name: EventNames,
+    #[serde(default)]
+    event_message: Vec<EventMessage>,
+    mailagent_key: Option<String>,
+    webhook_request_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum EventNames {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl EventNames {
+    //The name of the event of the message at `index`. A single name applies to every message.
+    fn get(&self, index: usize) -> Option<&str> {
+        match self {
+            EventNames::None => None,
+            EventNames::One(name) => Some(name),
+            EventNames::Many(names) if names.len() == 1 => Some(&names[0]),
+            EventNames::Many(names) => names.get(index).map(String::as_str),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EventMessage {
+    #[serde(default)]
+    email_info: EmailInfo,
+    #[serde(default)]
+    event_data: Vec<EventData>,
+    request_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventData {
+    //The event name, which takes precedence over `event_name` when present.
+    object: Option<String>,
+    #[serde(default)]
+    details: Vec<Value>,
+}
+
+/// Parses the body of a webhook call into its events, one per event and recipient.
+///
+/// Both a single payload and a JSON array of payloads are accepted. Events this crate does not model,
+/// and known events whose details have an unexpected shape, are returned as [`EventKind::Other`]
+/// rather than failing the whole call.
+///
+/// # Arguments
+///
+/// * `body` - The raw request body.
+///
+/// # Returns
+///
+/// This function returns a `Result`:
+/// * `Ok(Vec<WebhookEvent>)` - The events, in the order of the payload.
+/// * `Err(WebhookError)` - If the body cannot be parsed.
+///
+/// # Errors
+///
+/// This function can return the following errors:
+/// * `WebhookError::MalformedPayload` - If the body is not JSON or a payload does not have the expected shape.
+/// * `WebhookError::UnexpectedPayload` - If the body is neither a payload nor an array of payloads, or an event has no name.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::webhooks::parse_webhook;
+/// use zeptomail_rs::EventKind;
+///
+/// let body = br#"{
+///     "event_name": ["hardbounce"],
+///     "event_message": [{
+///         "email_info": {
+///             "client_reference": "order-1042",
+///             "subject": "Your order",
+///             "from": { "address": "sender@example.com", "name": "Shop" },
+///             "to": [{ "email_address": { "address": "jane@example.com", "name": "Jane" } }]
+///         },
+///         "event_data": [{
+///             "object": "hardbounce",
+///             "details": [{
+///                 "bounced_recipient": "jane@example.com",
+///                 "reason": "Mailbox does not exist",
+///                 "diagnostic_message": "550 5.1.1 User unknown",
+///                 "time": "2024-07-01T10:00:00+00:00"
+///             }]
+///         }],
+///         "request_id": "2d6f.1e0b"
+///     }],
+///     "mailagent_key": "1a2b3c",
+///     "webhook_request_id": "5e6f7a"
+/// }"#;
+///
+/// let events = parse_webhook(body).unwrap();
+///
+/// assert_eq!(events.len(), 1);
+/// assert!(matches!(&events[0].kind, EventKind::HardBounce(bounce) if bounce.reason.as_deref() == Some("Mailbox does not exist")));
+/// assert_eq!(events[0].recipient(), Some("jane@example.com"));
+/// assert_eq!(events[0].request_id.as_deref(), Some("2d6f.1e0b"));
+/// assert_eq!(events[0].client_reference.as_deref(), Some("order-1042"));
+/// ```
+///
+/// A bounce whose details have an unexpected shape does not fail the other events:
+///
+/// ```rust
+/// use zeptomail_rs::webhooks::parse_webhook;
+/// use zeptomail_rs::EventKind;
+///
+/// let body = br#"{
+///     "event_name": ["hardbounce"],
+///     "event_message": [{
+///         "event_data": [{
+///             "object": "hardbounce",
+///             "details": [
+///                 { "bounced_recipient": "jane@example.com", "reason": { "code": 550 } },
+///                 { "bounced_recipient": "john@example.com", "reason": "Mailbox does not exist" }
+///             ]
+///         }]
+///     }]
+/// }"#;
+///
+/// let events = parse_webhook(body).unwrap();
+///
+/// assert!(matches!(&events[0].kind, EventKind::Other { name, .. } if name == "hardbounce"));
+/// assert!(matches!(&events[1].kind, EventKind::HardBounce(_)));
+/// ```
+pub fn parse_webhook(body: &[u8]) -> Result<Vec<WebhookEvent>, WebhookError> {
+    let payloads: Vec<Payload> = match serde_json::from_slice::<Value>(body)? {
+        Value::Array(payloads) => payloads
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<Payload>, serde_json::Error>>()?,
+        payload @ Value::Object(_) => vec![serde_json::from_value(payload)?],
+        other => {
+            return Err(WebhookError::UnexpectedPayload(format!("expected an object or an array, not {}", other)));
+        }
+    };
+
+    let mut events: Vec<WebhookEvent> = Vec::new();
+    for payload in payloads {
+        for (index, message) in payload.event_message.into_iter().enumerate() {
+            for data in message.event_data {
+                let name: &str = data
+                    .object
+                    .as_deref()
+                    .or(payload.event_name.get(index))
+                    .ok_or_else(|| WebhookError::UnexpectedPayload("an event has no name".to_string()))?;
+
+                //An event without details still happened, e.g. a delivery ZeptoMail reports without a time.
+                let details: Vec<Value> = if data.details.is_empty() {
+                    vec![Value::Object(Default::default())]
+                } else {
+                    data.details
+                };
+                for details in details {
+                    events.push(WebhookEvent {
+                        kind: EventKind::from_details(name, details),
+                        request_id: message.request_id.clone(),
+                        client_reference: message.email_info.client_reference.clone(),
+                        email_info: message.email_info.clone(),
+                        mail_agent_key: payload.mailagent_key.clone(),
+                        webhook_request_id: payload.webhook_request_id.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(events)
+}
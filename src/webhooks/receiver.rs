@@ -0,0 +1,233 @@
+//! ## Webhook Receiver
+//! This module contains the pieces a webhook route is built from: the [`WebhookHandler`] trait an
+//! application implements, and the [`WebhookReceiver`] that verifies a call, parses it and
+//! dispatches its events to the handler.
+//!
+//! The receiver answers with the status codes ZeptoMail's retries depend on:
+//! * `200 OK` - The events were handled.
+//! * `401 Unauthorized` - The call could not be authenticated.
+//! * `400 Bad Request` - The body is not a webhook payload. Retrying would not change that.
+//! * `500 Internal Server Error` - The handler failed, so ZeptoMail sends the call again later.
+//!
+//! Because a failed call is retried as a whole, handlers should be idempotent, e.g. by remembering
+//! the `webhook_request_id` of the events they have processed.
+//!
+//! With the `axum` and `actix-web` features, the receiver can be mounted as a route directly.
+
+use std::error::Error;
+use std::fmt;
+
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::models::webhook::{Bounce, Click, Delivery, Open, SpamComplaint};
+use crate::webhooks::{parse_webhook, verify_token, WebhookError, WebhookVerifier, SIGNATURE_HEADER};
+use crate::{EventKind, WebhookEvent};
+
+/// The error a [`WebhookHandler`] returns when it could not process an event.
+pub type HandlerError = Box<dyn Error + Send + Sync>;
+
+/// Application code that reacts to webhook events, with a method per event type.
+///
+/// Every method does nothing by default, so a handler only implements the events it cares about.
+/// Returning an error makes the receiver answer `500`, so ZeptoMail retries the call.
+///
+/// # Example
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use zeptomail_rs::WebhookEvent;
+/// use zeptomail_rs::models::webhook::Bounce;
+/// use zeptomail_rs::webhooks::{HandlerError, WebhookHandler};
+///
+/// struct BounceLogger;
+///
+/// #[async_trait]
+/// impl WebhookHandler for BounceLogger {
+///     async fn on_hard_bounce(&self, event: &WebhookEvent, bounce: &Bounce) -> Result<(), HandlerError> {
+///         println!("{:?} bounced: {:?}", event.recipient(), bounce.reason);
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait WebhookHandler: Send + Sync + 'static {
+    async fn on_hard_bounce(&self, _event: &WebhookEvent, _bounce: &Bounce) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    async fn on_soft_bounce(&self, _event: &WebhookEvent, _bounce: &Bounce) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    async fn on_open(&self, _event: &WebhookEvent, _open: &Open) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    async fn on_click(&self, _event: &WebhookEvent, _click: &Click) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    async fn on_spam_complaint(
+        &self,
+        _event: &WebhookEvent,
+        _complaint: &SpamComplaint
+    ) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    async fn on_delivery(&self, _event: &WebhookEvent, _delivery: &Delivery) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    /// Called for events this crate does not model, and for known events whose details have an
+    /// unexpected shape.
+    async fn on_other(
+        &self,
+        _event: &WebhookEvent,
+        _name: &str,
+        _details: &Value
+    ) -> Result<(), HandlerError> {
+        Ok(())
+    }
+}
+
+/// How a [`WebhookReceiver`] authenticates calls.
+enum Authentication {
+    Insecure,
+    Signature(WebhookVerifier),
+    Token { header: String, token: String },
+}
+
+/// Verifies, parses and dispatches webhook calls to a [`WebhookHandler`].
+///
+/// # Example
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use reqwest::header::HeaderMap;
+/// use std::time::SystemTime;
+/// use zeptomail_rs::webhooks::{WebhookHandler, WebhookReceiver, WebhookVerifier, SIGNATURE_HEADER};
+///
+/// struct Handler;
+///
+/// #[async_trait]
+/// impl WebhookHandler for Handler {}
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let verifier = WebhookVerifier::new("your_webhook_secret");
+///     let body = br#"{"event_name":["email_open"],"event_message":[]}"#;
+///
+///     let mut headers = HeaderMap::new();
+///     headers.insert(SIGNATURE_HEADER, verifier.sign(body, SystemTime::now()).parse().unwrap());
+///
+///     let receiver = WebhookReceiver::new(Handler, verifier);
+///
+///     assert_eq!(receiver.receive(&headers, body).await, 200);
+///     assert_eq!(receiver.receive(&HeaderMap::new(), body).await, 401);
+/// }
+/// ```
+pub struct WebhookReceiver<H> {
+    handler: H,
+    authentication: Authentication,
+}
+
+impl<H: WebhookHandler> WebhookReceiver<H> {
+    /// A receiver that only accepts calls with a valid [`SIGNATURE_HEADER`].
+    pub fn new(handler: H, verifier: WebhookVerifier) -> Self {
+        WebhookReceiver {
+            handler,
+            authentication: Authentication::Signature(verifier),
+        }
+    }
+
+    /// A receiver that only accepts calls whose `header` header is `token`, for webhooks configured
+    /// with a custom header.
+    pub fn with_token(
+        handler: H,
+        header: impl Into<String>,
+        token: impl Into<String>
+    ) -> Self {
+        WebhookReceiver {
+            handler,
+            authentication: Authentication::Token {
+                header: header.into(),
+                token: token.into(),
+            },
+        }
+    }
+
+    /// A receiver that accepts every call, including forged ones. Only use it when the route is
+    /// authenticated some other way, e.g. by a proxy in front of it, or in tests.
+    pub fn insecure_unauthenticated(handler: H) -> Self {
+        WebhookReceiver {
+            handler,
+            authentication: Authentication::Insecure,
+        }
+    }
+
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Handles a webhook call and returns the status code to answer it with.
+    pub async fn receive(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8]
+    ) -> StatusCode {
+        let events: Vec<WebhookEvent> = match self.authenticate(headers, body).and_then(|_| parse_webhook(body)) {
+            Ok(events) => events,
+            Err(error) if error.is_unauthorized() => return StatusCode::UNAUTHORIZED,
+            Err(_) => return StatusCode::BAD_REQUEST,
+        };
+
+        for event in &events {
+            if self.dispatch(event).await.is_err() {
+                return StatusCode::INTERNAL_SERVER_ERROR;
+            }
+        }
+        StatusCode::OK
+    }
+
+    fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8]
+    ) -> Result<(), WebhookError> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        match &self.authentication {
+            Authentication::Insecure => Ok(()),
+            Authentication::Signature(verifier) => verifier.verify(header(SIGNATURE_HEADER), body),
+            Authentication::Token { header: name, token } => verify_token(token, header(name)),
+        }
+    }
+
+    async fn dispatch(&self, event: &WebhookEvent) -> Result<(), HandlerError> {
+        match &event.kind {
+            EventKind::HardBounce(bounce) => self.handler.on_hard_bounce(event, bounce).await,
+            EventKind::SoftBounce(bounce) => self.handler.on_soft_bounce(event, bounce).await,
+            EventKind::Open(open) => self.handler.on_open(event, open).await,
+            EventKind::Click(click) => self.handler.on_click(event, click).await,
+            EventKind::SpamComplaint(complaint) => self.handler.on_spam_complaint(event, complaint).await,
+            EventKind::Delivery(delivery) => self.handler.on_delivery(event, delivery).await,
+            EventKind::Other { name, details } => self.handler.on_other(event, name, details).await,
+        }
+    }
+}
+
+impl<H> fmt::Debug for WebhookReceiver<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let authentication: &str = match &self.authentication {
+            Authentication::Insecure => "insecure",
+            Authentication::Signature(_) => "signature",
+            Authentication::Token { .. } => "token",
+        };
+        f.debug_struct("WebhookReceiver")
+            .field("authentication", &authentication)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,281 @@
+//! ## Webhook Verification
+//! This module contains the checks that a webhook call was sent by ZeptoMail.
+//!
+//! When a webhook has a secret key, ZeptoMail signs every call and sends the signature in the
+//! [`SIGNATURE_HEADER`] header, e.g. `ts=1719828000000;s=<signature>;s-algorithm=HmacSHA256`. The
+//! `ts` is the time of the call in milliseconds since the Unix epoch, and the signature is the
+//! base64-encoded HMAC-SHA256, keyed with the secret, of `ts`, a `.` and the request body. Because the
+//! timestamp is signed, it cannot be changed without invalidating the signature, and calls outside
+//! the tolerance around the current time are rejected, so a captured call cannot be replayed later.
+//!
+//! This format has not been checked against ZeptoMail's published documentation or a captured call.
+//! The test vector on [`WebhookVerifier::sign`] was computed independently of this crate and pins the
+//! format as implemented. If calls from ZeptoMail fail with [`WebhookError::InvalidSignature`], compare
+//! their signature with it, and protect the webhook with [`verify_token`] in the meantime.
+//!
+//! Webhooks can also be protected with a static token in a custom header, which [`verify_token`]
+//! compares in constant time.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::webhooks::{parse_webhook, WebhookError};
+use crate::WebhookEvent;
+
+/// The header ZeptoMail sends the signature of a webhook call in.
+pub const SIGNATURE_HEADER: &str = "producer-signature";
+
+/// The only signature algorithm ZeptoMail uses.
+pub const SIGNATURE_ALGORITHM: &str = "HmacSHA256";
+
+const DEFAULT_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+/// Verifies the signatures of webhook calls with the webhook's secret key.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+/// use zeptomail_rs::webhooks::{WebhookError, WebhookVerifier};
+///
+/// let verifier = WebhookVerifier::new("your_webhook_secret").tolerance(Duration::from_secs(60));
+///
+/// let body = br#"{"event_name":["email_open"],"event_message":[]}"#;
+/// let signature = verifier.sign(body, SystemTime::now());
+///
+/// assert!(verifier.verify(Some(&signature), body).is_ok());
+/// assert!(matches!(verifier.verify(Some(&signature), b"{}"), Err(WebhookError::InvalidSignature)));
+/// assert!(verifier.verify(None, body).unwrap_err().is_unauthorized());
+/// ```
+///
+/// A captured call cannot be replayed, neither as it is nor with a new timestamp:
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+/// use zeptomail_rs::webhooks::{WebhookError, WebhookVerifier};
+///
+/// let verifier = WebhookVerifier::new("your_webhook_secret");
+///
+/// let body = br#"{"event_name":["email_open"],"event_message":[]}"#;
+/// let signed_at = SystemTime::now() - Duration::from_secs(60 * 60);
+/// let signature = verifier.sign(body, signed_at);
+///
+/// assert!(matches!(verifier.verify(Some(&signature), body), Err(WebhookError::StaleTimestamp { .. })));
+///
+/// let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+/// let (_, rest) = signature.split_once(';').unwrap();
+/// let retimed = format!("ts={};{}", now, rest);
+///
+/// assert!(matches!(verifier.verify(Some(&retimed), body), Err(WebhookError::InvalidSignature)));
+/// ```
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secret: Vec<u8>,
+    tolerance: Duration,
+}
+
+impl WebhookVerifier {
+    /// A verifier that accepts calls signed at most five minutes before or after the current time.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        WebhookVerifier {
+            secret: secret.into(),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Sets how far the signature timestamp may be from the current time.
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Checks the signature header of a webhook call against its raw body.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature_header` - The value of the [`SIGNATURE_HEADER`] header, if the call had one.
+    /// * `body` - The raw request body, before it is parsed.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    /// * `WebhookError::MissingSignature` - If the call has no signature header.
+    /// * `WebhookError::MalformedSignature` - If the header cannot be parsed or names another algorithm.
+    /// * `WebhookError::StaleTimestamp` - If the call was signed outside the tolerance.
+    /// * `WebhookError::InvalidSignature` - If the signature does not match the timestamp and body.
+    pub fn verify(
+        &self,
+        signature_header: Option<&str>,
+        body: &[u8]
+    ) -> Result<(), WebhookError> {
+        self.verify_at(signature_header, body, SystemTime::now())
+    }
+
+    /// Like [`verify`](Self::verify), with `now` as the current time.
+    pub fn verify_at(
+        &self,
+        signature_header: Option<&str>,
+        body: &[u8],
+        now: SystemTime
+    ) -> Result<(), WebhookError> {
+        let signature_header: &str = signature_header.ok_or(WebhookError::MissingSignature)?;
+        let signature: Signature = Signature::parse(signature_header)?;
+
+        let age: Duration = match now.duration_since(signature.timestamp) {
+            Ok(age) => age,
+            Err(e) => e.duration(),
+        };
+        if age > self.tolerance {
+            return Err(WebhookError::StaleTimestamp {
+                timestamp: signature.timestamp,
+                tolerance: self.tolerance,
+            });
+        }
+
+        //`verify_slice` compares in constant time.
+        self.mac(&signature.signed_timestamp, body)
+            .verify_slice(&signature.signature)
+            .map_err(|_| WebhookError::InvalidSignature)
+    }
+
+    /// Verifies a webhook call and parses its body.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the errors of [`verify`](Self::verify) and [`parse_webhook`].
+    pub fn verify_and_parse(
+        &self,
+        signature_header: Option<&str>,
+        body: &[u8]
+    ) -> Result<Vec<WebhookEvent>, WebhookError> {
+        self.verify(signature_header, body)?;
+        parse_webhook(body)
+    }
+
+    /// The signature header ZeptoMail would send for `body` at `timestamp`, e.g. to test webhook handlers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use zeptomail_rs::webhooks::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("your_webhook_secret");
+    ///
+    /// let body = br#"{"event_name":["email_open"],"event_message":[]}"#;
+    /// let signed_at = UNIX_EPOCH + Duration::from_millis(1_719_828_000_000);
+    ///
+    /// assert_eq!(
+    ///     verifier.sign(body, signed_at),
+    ///     "ts=1719828000000;s=6I3RWjpetwOiHWyez6oveaLAXqP2vSA7MJB0K1+7IPI=;s-algorithm=HmacSHA256"
+    /// );
+    /// assert!(verifier.verify_at(Some(&verifier.sign(body, signed_at)), body, signed_at).is_ok());
+    /// ```
+    pub fn sign(&self, body: &[u8], timestamp: SystemTime) -> String {
+        let millis: String = timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis().to_string();
+        let signature: String = STANDARD.encode(self.mac(&millis, body).finalize().into_bytes());
+        format!("ts={};s={};s-algorithm={}", millis, signature, SIGNATURE_ALGORITHM)
+    }
+
+    //The HMAC of `ts`, exactly as it appears in the header, a `.` and the body.
+    fn mac(&self, timestamp: &str, body: &[u8]) -> Hmac<Sha256> {
+        let mut mac: Hmac<Sha256> = Hmac::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(timestamp.as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        mac
+    }
+}
+
+impl fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("tolerance", &self.tolerance)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compares the token a webhook call carries, e.g. in a custom header, with the expected token in
+/// constant time.
+///
+/// # Errors
+///
+/// This function can return the following errors:
+/// * `WebhookError::MissingSignature` - If the call has no token.
+/// * `WebhookError::InvalidSignature` - If the token does not match.
+///
+/// # Example
+///
+/// ```rust
+/// use zeptomail_rs::webhooks::verify_token;
+///
+/// assert!(verify_token("your_webhook_token", Some("your_webhook_token")).is_ok());
+/// assert!(verify_token("your_webhook_token", Some("guess")).is_err());
+/// ```
+pub fn verify_token(expected: &str, token: Option<&str>) -> Result<(), WebhookError> {
+    let token: &str = token.ok_or(WebhookError::MissingSignature)?;
+    if constant_time_eq(expected.as_bytes(), token.as_bytes()) {
+        Ok(())
+    } else {
+        Err(WebhookError::InvalidSignature)
+    }
+}
+
+//Only the length of the tokens can be learned from the time this takes.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |difference, (x, y)| difference | (x ^ y)) == 0
+}
+
+struct Signature {
+    timestamp: SystemTime,
+    //`ts` as it appears in the header, which is what was signed.
+    signed_timestamp: String,
+    signature: Vec<u8>,
+}
+
+impl Signature {
+    fn parse(header: &str) -> Result<Self, WebhookError> {
+        let mut timestamp: Option<(SystemTime, String)> = None;
+        let mut signature: Option<Vec<u8>> = None;
+
+        for part in header.split([';', ',']).map(str::trim).filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| WebhookError::MalformedSignature(format!("{:?} is not a key=value pair", part)))?;
+            match key.trim() {
+                "ts" => {
+                    let millis: u64 = value.trim().parse().map_err(|_| {
+                        WebhookError::MalformedSignature(format!("{:?} is not a timestamp", value))
+                    })?;
+                    timestamp = Some((UNIX_EPOCH + Duration::from_millis(millis), value.trim().to_string()));
+                }
+                "s" => {
+                    let decoded: Vec<u8> = STANDARD.decode(value.trim()).map_err(|_| {
+                        WebhookError::MalformedSignature("the signature is not base64".to_string())
+                    })?;
+                    signature = Some(decoded);
+                }
+                "s-algorithm" if !value.trim().eq_ignore_ascii_case(SIGNATURE_ALGORITHM) => {
+                    return Err(WebhookError::MalformedSignature(format!("unsupported algorithm {:?}", value)));
+                }
+                _ => {}
+            }
+        }
+
+        let (timestamp, signed_timestamp) =
+            timestamp.ok_or_else(|| WebhookError::MalformedSignature("missing `ts`".to_string()))?;
+        Ok(Signature {
+            timestamp,
+            signed_timestamp,
+            signature: signature.ok_or_else(|| WebhookError::MalformedSignature("missing `s`".to_string()))?,
+        })
+    }
+}